@@ -0,0 +1,58 @@
+use ratatui::buffer::Buffer;
+
+// Terminals that are known to lack box-drawing characters and glyphs
+const LIMITED_TERMS: &[&str] = &["dumb", "linux", "vt52", "vt100", "vt102", "vt220", "ansi"];
+
+// Whether the terminal from $TERM likely can't show anything but ASCII
+pub fn limited_terminal() -> bool {
+    match std::env::var("TERM") {
+        Ok(term) => LIMITED_TERMS.contains(&term.as_str()),
+        Err(_) => false,
+    }
+}
+
+// The ASCII stand-in for a glyph the ui draws, None for anything else
+// (including non-ASCII in entries, which is left for the terminal to try)
+fn replacement(symbol: &str) -> Option<&'static str> {
+    Some(match symbol {
+        // Borders, rules and the finish timeline
+        "─" | "━" | "═" => "-",
+        "│" | "┃" | "║" => "|",
+        "┌" | "┐" | "└" | "┘" | "├" | "┤" | "┬" | "┴" | "┼" => "+",
+        "╭" | "╮" | "╰" | "╯" | "╔" | "╗" | "╚" | "╝" => "+",
+        // Arrows in hints and help
+        "←" => "<",
+        "→" => ">",
+        "↑" => "^",
+        "↓" => "v",
+        "▲" => "^",
+        "▼" => "v",
+        // Status glyphs and markers
+        "▶" => ">",
+        "⏸" => "=",
+        "◯" | "○" => "o",
+        "◉" | "●" | "•" => "*",
+        "⚠" => "!",
+        "▏" => "|",
+        "∓" => "~",
+        "≈" => "~",
+        "·" => ".",
+        "…" => ".",
+        "—" | "–" => "-",
+        // Calendar intensity and gauge fill
+        "░" => ".",
+        "▒" => ":",
+        "▓" => "%",
+        "█" | "▉" | "▊" | "▋" | "▌" | "▍" | "▎" => "#",
+        _ => return None,
+    })
+}
+
+// Swap what the ui drew for ASCII, after the fact so no widget is missed
+pub fn asciify(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        if let Some(ascii) = replacement(cell.symbol()) {
+            cell.set_symbol(ascii);
+        }
+    }
+}
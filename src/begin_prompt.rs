@@ -0,0 +1,89 @@
+use crate::time_input::parse_time_input;
+use chrono::Local;
+use requestty::{
+    prompt::{
+        backend::{ClearType, MoveDirection},
+        events::{KeyCode, KeyModifiers},
+        style::Color,
+        Answer, Answers, Backend, EventIterator, Prompt,
+    },
+    ErrorKind,
+};
+
+// A time input that shows what the typed time resolves to while typing,
+// e.g. '-0:15  → started 15:45'. Empty means now.
+#[derive(Debug)]
+pub struct BeginPrompt {
+    pub formats: Vec<String>,
+}
+
+// What to show after the input: the resolved time, or why it doesn't parse
+fn preview(input: &str, formats: &[String]) -> Result<String, String> {
+    if input.trim().is_empty() {
+        return Ok("→ now".to_string());
+    }
+    parse_time_input(input, formats)
+        .map(|time| format!("→ started {}", time.resolve(Local::now()).format("%H:%M")))
+}
+
+impl BeginPrompt {
+    fn render(&self, message: &str, input: &str, backend: &mut dyn Backend) -> std::io::Result<()> {
+        backend.move_cursor(MoveDirection::Column(0))?;
+        backend.clear(ClearType::UntilNewLine)?;
+        backend.set_fg(Color::LightGreen)?;
+        write!(backend, "? ")?;
+        backend.set_fg(Color::Reset)?;
+        write!(backend, "{} {}", message, input)?;
+
+        let (hint, color) = match preview(input, &self.formats) {
+            Ok(hint) => (hint, Color::DarkGrey),
+            Err(err) => (err, Color::Red),
+        };
+        let hint = format!("  {}", hint);
+        backend.set_fg(color)?;
+        write!(backend, "{}", hint)?;
+        backend.set_fg(Color::Reset)?;
+
+        // Back to the end of the input
+        backend.move_cursor(MoveDirection::Left(hint.chars().count() as u16))?;
+        backend.flush()
+    }
+}
+
+impl Prompt for BeginPrompt {
+    fn ask(
+        self,
+        message: String,
+        _: &Answers,
+        backend: &mut dyn Backend,
+        events: &mut dyn EventIterator,
+    ) -> requestty::Result<Option<Answer>> {
+        let mut input = String::new();
+        backend.enable_raw_mode()?;
+
+        let result = loop {
+            self.render(&message, &input, backend)?;
+
+            let key = events.next_event()?;
+            match key.code {
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    break Err(ErrorKind::Interrupted);
+                }
+                KeyCode::Char(c) => input.push(c),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                // Only accept what resolves, the preview already says why not
+                KeyCode::Enter if preview(&input, &self.formats).is_ok() => break Ok(()),
+                _ => {}
+            }
+        };
+
+        // Leave the resolved time on screen, like the built-in prompts leave their answer
+        self.render(&message, &input, backend)?;
+        write!(backend, "\r\n")?;
+        backend.disable_raw_mode()?;
+
+        result.map(|_| Some(Answer::String(input)))
+    }
+}
@@ -0,0 +1,31 @@
+// Digits drawn five rows high out of blocks, for the focus mode timer
+pub const HEIGHT: usize = 5;
+
+fn glyph(c: char) -> [&'static str; HEIGHT] {
+    match c {
+        '0' => ["███", "█ █", "█ █", "█ █", "███"],
+        '1' => ["  █", "  █", "  █", "  █", "  █"],
+        '2' => ["███", "  █", "███", "█  ", "███"],
+        '3' => ["███", "  █", "███", "  █", "███"],
+        '4' => ["█ █", "█ █", "███", "  █", "  █"],
+        '5' => ["███", "█  ", "███", "  █", "███"],
+        '6' => ["███", "█  ", "███", "█ █", "███"],
+        '7' => ["███", "  █", "  █", "  █", "  █"],
+        '8' => ["███", "█ █", "███", "█ █", "███"],
+        '9' => ["███", "█ █", "███", "  █", "███"],
+        ':' => [" ", "█", " ", "█", " "],
+        _ => [" ", " ", " ", " ", " "],
+    }
+}
+
+// 'text' as HEIGHT lines of block glyphs, one column apart
+pub fn lines(text: &str) -> Vec<String> {
+    (0..HEIGHT)
+        .map(|row| {
+            text.chars()
+                .map(|c| glyph(c)[row])
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
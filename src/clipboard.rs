@@ -0,0 +1,16 @@
+use arboard::Clipboard;
+
+// Copy 'text' to the system clipboard. There may be no clipboard at all
+// (headless, SSH), so failures come back as a message for the status line.
+// On X11 the contents only live as long as the handle, which is why the
+// caller keeps it around between copies.
+pub fn copy(handle: &mut Option<Clipboard>, text: &str) -> Result<(), String> {
+    if handle.is_none() {
+        *handle = Some(Clipboard::new().map_err(|err| format!("No clipboard available: {}", err))?);
+    }
+
+    handle
+        .as_mut()
+        .map_or(Ok(()), |clipboard| clipboard.set_text(text))
+        .map_err(|err| format!("Copying failed: {}", err))
+}
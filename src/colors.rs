@@ -0,0 +1,51 @@
+use ratatui::style::Color;
+use std::{cell::RefCell, collections::HashMap};
+
+// Readable on both dark and light backgrounds
+const PALETTE: [Color; 10] = [
+    Color::Cyan,
+    Color::Green,
+    Color::Yellow,
+    Color::Magenta,
+    Color::Blue,
+    Color::Red,
+    Color::LightCyan,
+    Color::LightGreen,
+    Color::LightMagenta,
+    Color::LightBlue,
+];
+
+// A color per project, from a hash of its name so it's the same every run
+pub struct ProjectColors {
+    enabled: bool,
+    assigned: RefCell<HashMap<String, Color>>,
+}
+
+impl ProjectColors {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            assigned: RefCell::new(HashMap::new()),
+        }
+    }
+
+    // None with coloring turned off
+    pub fn get(&self, project: &str) -> Option<Color> {
+        if !self.enabled {
+            return None;
+        }
+
+        let mut assigned = self.assigned.borrow_mut();
+        let color = assigned
+            .entry(project.to_string())
+            .or_insert_with(|| PALETTE[(fnv1a(project) % PALETTE.len() as u64) as usize]);
+        Some(*color)
+    }
+}
+
+// FNV-1a, unlike std's hasher it's stable across Rust versions
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
@@ -0,0 +1,114 @@
+use crate::entry::Entry;
+use ratatui::layout::Constraint;
+use std::cmp::Ordering;
+
+// Columns the List table can show, picked and ordered by list_columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Project,
+    Task,
+    Begin,
+    End,
+    Duration,
+    Ago,
+    Note,
+    Tags,
+}
+
+impl Column {
+    pub const ALL: [Column; 8] = [
+        Column::Project,
+        Column::Task,
+        Column::Begin,
+        Column::End,
+        Column::Duration,
+        Column::Ago,
+        Column::Note,
+        Column::Tags,
+    ];
+
+    // Name used in the config file
+    pub fn name(self) -> &'static str {
+        match self {
+            Column::Project => "project",
+            Column::Task => "task",
+            Column::Begin => "begin",
+            Column::End => "end",
+            Column::Duration => "duration",
+            Column::Ago => "ago",
+            Column::Note => "note",
+            Column::Tags => "tags",
+        }
+    }
+
+    pub fn header(self) -> &'static str {
+        match self {
+            Column::Project => "Project",
+            Column::Task => "Task",
+            Column::Begin => "Begin",
+            Column::End => "Finish",
+            Column::Duration => "Duration",
+            Column::Ago => "Ago",
+            Column::Note => "Note",
+            Column::Tags => "Tags",
+        }
+    }
+
+    // Orders entries by this column, for the ones 'o' cycles through
+    pub fn compare(self, a: &Entry, b: &Entry) -> Ordering {
+        match self {
+            Column::Project => a.project.to_lowercase().cmp(&b.project.to_lowercase()),
+            Column::Duration => a.minutes().cmp(&b.minutes()),
+            _ => a.begin.cmp(&b.begin),
+        }
+    }
+
+    pub fn width(self) -> Constraint {
+        match self {
+            Column::Project | Column::Task | Column::Tags => Constraint::Min(10),
+            Column::Begin => Constraint::Length(16),
+            Column::End => Constraint::Length(7),
+            Column::Duration => Constraint::Length(8),
+            Column::Ago => Constraint::Length(10),
+            Column::Note => Constraint::Min(16),
+        }
+    }
+}
+
+// What the List can be sorted by, in the order 'o' cycles through
+pub const SORTABLE: [Column; 3] = [Column::Project, Column::Begin, Column::Duration];
+
+// Everything but the note, which has the detail pane
+pub const DEFAULT_COLUMNS: [Column; 6] = [
+    Column::Project,
+    Column::Task,
+    Column::Begin,
+    Column::End,
+    Column::Duration,
+    Column::Ago,
+];
+
+// Resolve the list_columns config, rejecting unknown names. Unset or empty
+// means the defaults.
+pub fn from_config(names: &[String]) -> Result<Vec<Column>, String> {
+    if names.is_empty() {
+        return Ok(DEFAULT_COLUMNS.to_vec());
+    }
+
+    names
+        .iter()
+        .map(|name| {
+            Column::ALL
+                .into_iter()
+                .find(|column| column.name() == name.trim().to_lowercase())
+                .ok_or_else(|| {
+                    let known: Vec<&str> = Column::ALL.iter().map(|c| c.name()).collect();
+                    format!(
+                        "Unknown column '{}' in list_columns, expected one of {}",
+                        name,
+                        known.join(", ")
+                    )
+                })
+        })
+        .collect()
+}
@@ -0,0 +1,116 @@
+use crate::entry::{parse_list, project_totals};
+use crate::error::ZeoxError;
+use crate::runner::ZeitRunner;
+use chrono::{Datelike, Duration, Local, Months, NaiveDate};
+
+// How much the Stats comparison puts side by side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Window {
+    Day,
+    Week,
+    Month,
+}
+
+impl Window {
+    pub fn next(self) -> Self {
+        match self {
+            Window::Day => Window::Week,
+            Window::Week => Window::Month,
+            Window::Month => Window::Day,
+        }
+    }
+
+    // Heading of the current and the previous column
+    pub fn labels(self) -> (&'static str, &'static str) {
+        match self {
+            Window::Day => ("Today", "Yesterday"),
+            Window::Week => ("This week", "Last week"),
+            Window::Month => ("This month", "Last month"),
+        }
+    }
+
+    // First day of the window 'today' is in, and of the one before it.
+    // Weeks start on Monday.
+    fn starts(self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            Window::Day => (today, today - Duration::days(1)),
+            Window::Week => {
+                let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+                (monday, monday - Duration::days(7))
+            }
+            Window::Month => {
+                let first = today.with_day(1).unwrap_or(today);
+                (first, first - Months::new(1))
+            }
+        }
+    }
+}
+
+// Per-project minutes in the current window so far and the whole previous
+// one, busiest first
+pub struct Comparison {
+    pub window: Window,
+    pub rows: Vec<(String, u64, u64)>,
+}
+
+impl Comparison {
+    // Two 'zeit list' calls, one per window
+    pub fn fetch(runner: &dyn ZeitRunner, window: Window) -> Result<Self, ZeoxError> {
+        let (current, previous) = window.starts(Local::now().date_naive());
+        let midnight = |day: NaiveDate| {
+            day.and_hms_opt(0, 0, 0)
+                .and_then(|time| time.and_local_timezone(Local).earliest())
+                .map(|time| time.to_rfc3339())
+                .ok_or_else(|| ZeoxError::from(format!("No local midnight on {}", day)))
+        };
+        let (current, previous) = (midnight(current)?, midnight(previous)?);
+
+        let now = project_totals(&parse_list(&runner.run(&[
+            "list",
+            "--since",
+            &current,
+            "--no-colors",
+        ])?));
+        let before = project_totals(&parse_list(&runner.run(&[
+            "list",
+            "--since",
+            &previous,
+            "--until",
+            &current,
+            "--no-colors",
+        ])?));
+
+        let mut rows: Vec<(String, u64, u64)> = now
+            .into_iter()
+            .map(|(project, minutes)| {
+                let earlier = before
+                    .iter()
+                    .find(|(name, _)| *name == project)
+                    .map_or(0, |(_, minutes)| *minutes);
+                (project, minutes, earlier)
+            })
+            .collect();
+        // Projects that were only worked on in the previous window
+        for (project, minutes) in before {
+            if !rows.iter().any(|(name, _, _)| *name == project) {
+                rows.push((project, 0, minutes));
+            }
+        }
+
+        Ok(Self { window, rows })
+    }
+
+    pub fn totals(&self) -> (u64, u64) {
+        self.rows
+            .iter()
+            .fold((0, 0), |(now, before), (_, current, previous)| {
+                (now + current, before + previous)
+            })
+    }
+}
+
+// Change from 'previous' to 'current' in percent, None when there's nothing
+// to compare against
+pub fn delta(current: u64, previous: u64) -> Option<f64> {
+    (previous > 0).then(|| (current as f64 - previous as f64) / previous as f64 * 100.0)
+}
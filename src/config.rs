@@ -0,0 +1,349 @@
+use crate::{hooks::Hooks, profile::Profile};
+use chrono::NaiveTime;
+use serde::Deserialize;
+use std::{collections::HashMap, env, fs, io, path::PathBuf};
+use toml::{Table, Value};
+
+// toml errors can span lines with a snippet, one line is enough for a banner
+fn first_line(message: &str) -> String {
+    message.lines().next().unwrap_or(message).trim().to_string()
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    // Minutes without any input before an active session is flagged as idle,
+    // unset disables idle detection
+    pub idle_timeout_minutes: Option<u64>,
+    // chrono formats accepted for absolute begin/finish times, on top of
+    // relative offsets and RFC 3339
+    pub time_formats: Vec<String>,
+    // Round begin/finish times to this many minutes, unset or 0 keeps them exact
+    pub round_to_minutes: Option<u32>,
+    // A session found running for this many hours on launch is flagged as
+    // likely forgotten, 0 never flags one
+    pub long_session_hours: u64,
+    // What to do when starting a session while another one is running
+    pub on_start_while_tracking: StartGuard,
+    // Which actions ask before going ahead
+    pub confirm: Confirm,
+    // How project names typed when starting are cased, they're always trimmed
+    pub project_case: ProjectCase,
+    // Offer the recently used projects when starting instead of a blank prompt
+    pub recent_picker: bool,
+    // Screen shown on launch: "main", "list" or "stats"
+    pub startup_screen: String,
+    // Color projects consistently across the list and stats
+    pub project_colors: bool,
+    // Columns of the List table in order, empty for the defaults. 'tags'
+    // are the #words in the notes, zeit has none of its own.
+    pub list_columns: Vec<String>,
+    // Draw with plain ASCII instead of box-drawing characters and glyphs,
+    // unset to decide from the terminal
+    pub ascii_mode: Option<bool>,
+    // Hours to aim for per day, shown as a gauge on the main screen
+    pub daily_goal_hours: Option<f64>,
+    // Shell commands run on session events
+    pub hooks: Hooks,
+    // Length of a pomodoro started with 'p'
+    pub pomodoro_minutes: u64,
+    // Desktop notifications when sessions start and finish
+    pub notifications: bool,
+    // With notifications on, also remind every hour of continuous tracking
+    pub hourly_reminder: bool,
+    // Finish a running session when the clock passes this time, e.g. "18:30"
+    pub auto_finish_at: Option<String>,
+    // Ring the terminal bell when a session finishes
+    pub notify_on_finish: bool,
+    // Key overrides per action name, e.g. quit = "x"
+    pub keys: HashMap<String, char>,
+    // Projects started by a digit on the main screen, e.g. 1 = "acme"
+    pub quick_projects: HashMap<String, String>,
+    // Only show the zeit commands that would change data, like --dry-run
+    pub dry_run: bool,
+    // What 'zeox --oneline' prints while nothing is tracked
+    pub oneline_idle: String,
+    // Named zeit databases to switch between, the first one is active on start
+    pub profiles: Vec<Profile>,
+    // Billable rate per hour, shown as earnings on the stats screen
+    pub hourly_rate: Option<f64>,
+    pub currency: String,
+    // Per-project rates overriding hourly_rate
+    pub rates: HashMap<String, f64>,
+    // How zeit is run, e.g. ["flatpak", "run", "zeit"] for a wrapper; the
+    // subcommand and its arguments are appended
+    pub zeit_command: Vec<String>,
+    // Seconds before a hanging zeit call is killed, 0 waits forever;
+    // ZEOX_ZEIT_TIMEOUT overrides it
+    pub zeit_timeout_secs: u64,
+    // How often to retry a zeit call whose stderr contains one of
+    // transient_errors, with a growing pause in between
+    pub retries: u32,
+    pub transient_errors: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StartGuard {
+    // Finish the running session before starting the new one
+    AutoFinish,
+    // Ask whether to finish it first or leave it to zeit
+    #[default]
+    Prompt,
+    // Refuse to start
+    Abort,
+}
+
+// [confirm] in zeox.toml, everything that can't be undone asks by default
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct Confirm {
+    // 'd' on the List
+    pub delete: bool,
+    pub quit_while_tracking: bool,
+    // 'D' on the List, confirmed by typing the count
+    pub batch_delete: bool,
+}
+
+impl Default for Confirm {
+    fn default() -> Self {
+        Self {
+            delete: true,
+            quit_while_tracking: true,
+            batch_delete: true,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProjectCase {
+    // As typed
+    #[default]
+    Keep,
+    // e.g. 'acme corp'
+    Lower,
+    // e.g. 'Acme Corp'
+    Title,
+}
+
+impl ProjectCase {
+    pub fn apply(self, project: &str) -> String {
+        let project = project.trim();
+        match self {
+            ProjectCase::Keep => project.to_string(),
+            ProjectCase::Lower => project.to_lowercase(),
+            ProjectCase::Title => project
+                .split(' ')
+                .map(|word| {
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        Some(first) => first
+                            .to_uppercase()
+                            .chain(chars.flat_map(char::to_lowercase))
+                            .collect(),
+                        None => String::new(),
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join(" "),
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            idle_timeout_minutes: None,
+            time_formats: vec![
+                "%H:%M".to_string(),
+                "%Y-%m-%d %H:%M".to_string(),
+                "%Y-%m-%dT%H:%M".to_string(),
+            ],
+            round_to_minutes: None,
+            long_session_hours: 10,
+            on_start_while_tracking: StartGuard::default(),
+            confirm: Confirm::default(),
+            project_case: ProjectCase::default(),
+            recent_picker: false,
+            startup_screen: "main".to_string(),
+            project_colors: true,
+            list_columns: Vec::new(),
+            ascii_mode: None,
+            daily_goal_hours: None,
+            hooks: Hooks::default(),
+            pomodoro_minutes: 25,
+            notifications: false,
+            hourly_reminder: false,
+            auto_finish_at: None,
+            notify_on_finish: false,
+            keys: HashMap::new(),
+            quick_projects: HashMap::new(),
+            dry_run: false,
+            oneline_idle: "◯ idle".to_string(),
+            profiles: Vec::new(),
+            hourly_rate: None,
+            currency: "$".to_string(),
+            rates: HashMap::new(),
+            zeit_command: vec!["zeit".to_string()],
+            zeit_timeout_secs: 10,
+            retries: 2,
+            transient_errors: vec!["database is locked".to_string()],
+        }
+    }
+}
+
+impl Config {
+    // Looks for 'zeox --theme', each a preset of the appearance settings
+    pub const THEMES: [&'static str; 4] = ["default", "ascii", "mono", "plain"];
+
+    // Override the appearance settings with the named theme, false when
+    // there's no such theme. Only lasts for this launch.
+    pub fn apply_theme(&mut self, name: &str) -> bool {
+        let (ascii, colors) = match name {
+            // As configured
+            "default" => return true,
+            "ascii" => (true, self.project_colors),
+            "mono" => (self.ascii_mode.unwrap_or(false), false),
+            "plain" => (true, false),
+            _ => return false,
+        };
+        self.ascii_mode = Some(ascii);
+        self.project_colors = colors;
+        true
+    }
+
+    // Location of the config file, honoring XDG_CONFIG_HOME
+    pub fn path() -> Option<PathBuf> {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .map(|dir| dir.join("zeox").join("zeox.toml"))
+    }
+
+    // auto_finish_at as a time of day, an unparseable value is a config error
+    pub fn auto_finish_time(&self) -> Result<Option<NaiveTime>, String> {
+        self.auto_finish_at
+            .as_deref()
+            .map(|at| {
+                NaiveTime::parse_from_str(at.trim(), "%H:%M")
+                    .map_err(|_| format!("Invalid auto_finish_at '{}', expected HH:MM", at))
+            })
+            .transpose()
+    }
+
+    // quick_projects by digit, in order. Only 1-9 can be bound, and to a
+    // project with a name.
+    pub fn quick_projects(&self) -> Result<Vec<(char, String)>, String> {
+        let mut bound = Vec::new();
+        for (digit, project) in &self.quick_projects {
+            let key = match digit.trim().chars().collect::<Vec<_>>()[..] {
+                [key @ '1'..='9'] => key,
+                _ => return Err(format!("'{}' isn't a digit from 1 to 9", digit)),
+            };
+            if project.trim().is_empty() {
+                return Err(format!("{} is bound to an empty project name", key));
+            }
+            bound.push((key, project.trim().to_string()));
+        }
+        bound.sort();
+        Ok(bound)
+    }
+
+    pub fn rate(&self, project: &str) -> Option<f64> {
+        self.rates.get(project).copied().or(self.hourly_rate)
+    }
+
+    pub fn has_rates(&self) -> bool {
+        self.hourly_rate.is_some() || !self.rates.is_empty()
+    }
+
+    // What the given per-project minutes earn, projects without a rate earn nothing
+    pub fn earnings(&self, totals: &[(String, u64)]) -> f64 {
+        totals
+            .iter()
+            .filter_map(|(project, minutes)| {
+                self.rate(project).map(|rate| *minutes as f64 / 60.0 * rate)
+            })
+            .sum()
+    }
+
+    // The config and what was wrong with it. Keys that don't fit are left at
+    // their defaults rather than failing the whole file, only an unreadable
+    // file is an error.
+    pub fn load() -> Result<(Self, Vec<String>), Box<dyn std::error::Error>> {
+        let Some(path) = Self::path() else {
+            return Ok((Self::default(), Vec::new()));
+        };
+
+        // A missing config file just means defaults
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Ok((Self::default(), Vec::new()))
+            }
+            Err(err) => return Err(format!("Failed to read {}: {}", path.display(), err).into()),
+        };
+
+        match contents.parse::<Table>() {
+            Ok(table) => Ok(Self::from_table(table)),
+            Err(err) => Ok((
+                Self::default(),
+                vec![format!(
+                    "{} isn't valid TOML, using the defaults: {}",
+                    path.display(),
+                    first_line(&err.to_string())
+                )],
+            )),
+        }
+    }
+
+    // Drop every key that wouldn't load on its own, and within tables every
+    // entry, so the rest still applies
+    fn from_table(table: Table) -> (Self, Vec<String>) {
+        let mut problems = Vec::new();
+        let mut kept = Table::new();
+
+        for (key, value) in table {
+            let value = match Self::check(&key, value.clone()) {
+                Ok(()) => value,
+                Err(err) => match value {
+                    Value::Table(entries) => {
+                        let mut fitting = Table::new();
+                        for (name, entry) in entries {
+                            let single = Value::Table(Table::from_iter([(name.clone(), entry)]));
+                            match Self::check(&key, single.clone()) {
+                                Ok(()) => {
+                                    fitting.extend(single.as_table().cloned().unwrap_or_default())
+                                }
+                                Err(err) => problems.push(format!("{}.{}: {}", key, name, err)),
+                            }
+                        }
+                        Value::Table(fitting)
+                    }
+                    _ => {
+                        problems.push(format!("{}: {}", key, err));
+                        continue;
+                    }
+                },
+            };
+            kept.insert(key, value);
+        }
+
+        match Value::Table(kept).try_into() {
+            Ok(config) => (config, problems),
+            Err(err) => {
+                problems.push(first_line(&err.to_string()));
+                (Self::default(), problems)
+            }
+        }
+    }
+
+    // Whether 'key = value' alone makes a valid config
+    fn check(key: &str, value: Value) -> Result<(), String> {
+        Value::Table(Table::from_iter([(key.to_string(), value)]))
+            .try_into::<Self>()
+            .map(|_| ())
+            .map_err(|err| first_line(&err.to_string()))
+    }
+}
@@ -0,0 +1,301 @@
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, Utc};
+use std::collections::HashMap;
+
+// One line of 'zeit list' output:
+// <id> <task> on <project> from <begin> to <finish> (<h:mm>h) [running]
+// optionally followed by indented lines of notes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub id: String,
+    pub task: String,
+    pub project: String,
+    pub begin: DateTime<FixedOffset>,
+    pub finish: DateTime<FixedOffset>,
+    pub running: bool,
+    pub notes: String,
+}
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M %z";
+
+impl Entry {
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        let (id, rest) = line.split_once(' ')?;
+        let (name, rest) = rest.rsplit_once(" from ")?;
+        let (task, project) = name.rsplit_once(" on ")?;
+        let (begin, rest) = rest.split_once(" to ")?;
+        let (finish, rest) = rest.split_once(" (")?;
+
+        Some(Self {
+            id: id.to_string(),
+            task: task.trim().to_string(),
+            project: project.trim().to_string(),
+            begin: DateTime::parse_from_str(begin.trim(), TIME_FORMAT).ok()?,
+            finish: DateTime::parse_from_str(finish.trim(), TIME_FORMAT).ok()?,
+            running: rest.contains("[running]"),
+            notes: String::new(),
+        })
+    }
+
+    // A running entry's finish is just when zeit printed it, so count up to now
+    pub fn duration(&self) -> Duration {
+        let finish = if self.running {
+            Utc::now().fixed_offset()
+        } else {
+            self.finish
+        };
+        (finish - self.begin).max(Duration::zero())
+    }
+
+    pub fn minutes(&self) -> u64 {
+        self.duration().num_minutes() as u64
+    }
+
+    // zeit has no tags of its own, so these are the '#words' in the notes,
+    // each once and without trailing punctuation
+    pub fn tags(&self) -> Vec<&str> {
+        let mut tags: Vec<&str> = Vec::new();
+        for word in self.notes.split_whitespace() {
+            let tag = word.trim_end_matches(|c: char| !c.is_alphanumeric());
+            if tag.len() > 1 && tag.starts_with('#') && !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        tags
+    }
+}
+
+// Parse every recognizable entry, skipping headers, totals and blank lines.
+// Indented lines that aren't entries belong to the notes of the one above.
+pub fn parse_list(output: &str) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = Vec::new();
+    for line in output.lines() {
+        push_line(&mut entries, line);
+    }
+    entries
+}
+
+// Parse one more line of 'zeit list' output onto 'entries', so they can be
+// built up while the output streams in
+pub fn push_line(entries: &mut Vec<Entry>, line: &str) {
+    if let Some(entry) = Entry::parse(line) {
+        entries.push(entry);
+    } else if line.starts_with(char::is_whitespace) && !line.trim().is_empty() {
+        if let Some(entry) = entries.last_mut() {
+            if !entry.notes.is_empty() {
+                entry.notes.push('\n');
+            }
+            entry.notes.push_str(line.trim());
+        }
+    }
+}
+
+// Which entries the List screen shows
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Filter {
+    pub project: Option<String>,
+    pub date: Option<NaiveDate>,
+}
+
+impl Filter {
+    pub fn is_active(&self) -> bool {
+        self.project.is_some() || self.date.is_some()
+    }
+
+    pub fn matches(&self, entry: &Entry) -> bool {
+        self.project.as_ref().is_none_or(|p| *p == entry.project)
+            && self.date.is_none_or(|d| d == entry.begin.date_naive())
+    }
+
+    // e.g. 'project:acme date:2024-10-03'
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(project) = &self.project {
+            parts.push(format!("project:{}", project));
+        }
+        if let Some(date) = self.date {
+            parts.push(format!("date:{}", date));
+        }
+        parts.join(" ")
+    }
+}
+
+// Total minutes per begin date
+pub fn day_totals(entries: &[Entry]) -> HashMap<NaiveDate, u64> {
+    let mut totals = HashMap::new();
+    for entry in entries {
+        *totals.entry(entry.begin.date_naive()).or_insert(0) += entry.minutes();
+    }
+    totals
+}
+
+// Total minutes per project, largest first
+pub fn project_totals(entries: &[Entry]) -> Vec<(String, u64)> {
+    let mut totals: Vec<(String, u64)> = Vec::new();
+
+    for entry in entries {
+        match totals
+            .iter_mut()
+            .find(|(project, _)| *project == entry.project)
+        {
+            Some((_, minutes)) => *minutes += entry.minutes(),
+            None => totals.push((entry.project.clone(), entry.minutes())),
+        }
+    }
+
+    totals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    totals
+}
+
+// How many sessions lasted how long, shortest bucket first
+pub fn length_buckets(entries: &[Entry]) -> [(&'static str, u64); 5] {
+    let mut buckets = [
+        ("<15m", 0),
+        ("15-30m", 0),
+        ("30-60m", 0),
+        ("1-2h", 0),
+        ("2h+", 0),
+    ];
+    for entry in entries {
+        let bucket = match entry.minutes() {
+            0..15 => 0,
+            15..30 => 1,
+            30..60 => 2,
+            60..120 => 3,
+            _ => 4,
+        };
+        buckets[bucket].1 += 1;
+    }
+    buckets
+}
+
+// Distinct projects, most recently started first
+pub fn recent_projects(entries: &[Entry]) -> Vec<String> {
+    let mut sorted: Vec<&Entry> = entries.iter().collect();
+    sorted.sort_by_key(|entry| std::cmp::Reverse(entry.begin));
+
+    let mut projects: Vec<String> = Vec::new();
+    for entry in sorted {
+        if !projects.contains(&entry.project) {
+            projects.push(entry.project.clone());
+        }
+    }
+    projects
+}
+
+// How long ago 'time' was, e.g. '5m ago', '2h ago', 'yesterday', '3 days ago',
+// falling back to the date for anything older than a week
+pub fn humanize_relative(time: DateTime<Utc>) -> String {
+    humanize_relative_to(time, Utc::now())
+}
+
+fn humanize_relative_to(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let minutes = (now - time).num_minutes();
+
+    match minutes {
+        i64::MIN..=0 => "just now".to_string(),
+        1..=59 => format!("{}m ago", minutes),
+        60..=1439 => format!("{}h ago", minutes / 60),
+        1440..=2879 => "yesterday".to_string(),
+        2880..=10079 => format!("{} days ago", minutes / 1440),
+        _ => time.format("%Y-%m-%d").to_string(),
+    }
+}
+
+// Format minutes as e.g. '12h 34m'
+pub fn format_duration(minutes: u64) -> String {
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+// Format an amount of money as e.g. '$1,234.50'
+pub fn format_money(amount: f64, currency: &str) -> String {
+    let cents = (amount * 100.0).round() as u64;
+    let whole = (cents / 100).to_string();
+
+    // Thousands separators, counted from the right
+    let mut grouped = String::new();
+    for (i, digit) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    format!("{}{}.{:02}", currency, grouped, cents % 100)
+}
+
+// The running session as reported by 'zeit tracking':
+// ▶ tracking <task> on <project> for <h:mm>h
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tracking {
+    pub task: String,
+    pub project: String,
+    pub elapsed_minutes: u64,
+}
+
+impl Tracking {
+    pub fn parse(status: &str) -> Option<Self> {
+        let line = status
+            .lines()
+            .find(|l| !l.trim().is_empty())?
+            .trim_start_matches(|c: char| !c.is_alphabetic());
+        let rest = line.strip_prefix("tracking")?;
+        let (name, elapsed) = rest.rsplit_once(" for ")?;
+        let (task, project) = name.rsplit_once(" on ")?;
+
+        Some(Self {
+            task: task.trim().to_string(),
+            project: project.trim().to_string(),
+            elapsed_minutes: parse_hours_minutes(elapsed.trim().trim_end_matches('h')).unwrap_or(0),
+        })
+    }
+}
+
+// zeit prints durations as 'h:mm'
+pub fn parse_hours_minutes(text: &str) -> Option<u64> {
+    let (hours, minutes) = text.trim().split_once(':')?;
+    Some(hours.parse::<u64>().ok()? * 60 + minutes.parse::<u64>().ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn humanize_relative_boundaries() {
+        let now = Utc.with_ymd_and_hms(2024, 10, 16, 12, 0, 0).unwrap();
+        let ago = |minutes: i64| humanize_relative_to(now - Duration::minutes(minutes), now);
+
+        assert_eq!(ago(0), "just now");
+        assert_eq!(ago(1), "1m ago");
+        assert_eq!(ago(59), "59m ago");
+        assert_eq!(ago(60), "1h ago");
+        assert_eq!(ago(1439), "23h ago");
+        assert_eq!(ago(1440), "yesterday");
+        assert_eq!(ago(2880), "2 days ago");
+        assert_eq!(ago(10079), "6 days ago");
+        assert_eq!(ago(10080), "2024-10-09");
+    }
+
+    #[test]
+    fn tags_come_from_the_notes() {
+        let mut entry = Entry::parse(
+            "a1 review on acme from 2024-10-16 09:00 +0000 to 2024-10-16 10:05 +0000 (1:05h)",
+        )
+        .unwrap();
+        assert!(entry.tags().is_empty());
+
+        entry.notes = "#client call, #billable.\nfollow-up #client # and #".to_string();
+        assert_eq!(entry.tags(), ["#client", "#billable"]);
+    }
+
+    #[test]
+    fn humanize_relative_future_is_just_now() {
+        let now = Utc.with_ymd_and_hms(2024, 10, 16, 12, 0, 0).unwrap();
+        assert_eq!(
+            humanize_relative_to(now + Duration::minutes(30), now),
+            "just now"
+        );
+    }
+}
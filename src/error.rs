@@ -0,0 +1,91 @@
+use std::{fmt, io, time::Duration};
+
+#[derive(Debug)]
+pub enum ZeoxError {
+    // zeit ran but exited unsuccessfully
+    Command {
+        argv: Vec<String>,
+        stderr: String,
+    },
+    // zeit could not be launched at all
+    Spawn {
+        argv: Vec<String>,
+        source: io::Error,
+    },
+    // zeit didn't finish in time and was killed
+    Timeout {
+        argv: Vec<String>,
+        after: Duration,
+    },
+    // Dry-run mode: the command that would have run
+    DryRun {
+        argv: Vec<String>,
+    },
+    // Anything else worth telling the user
+    Message(String),
+}
+
+impl ZeoxError {
+    // The exact command line that was attempted, if any
+    pub fn argv(&self) -> Option<&[String]> {
+        match self {
+            ZeoxError::Command { argv, .. }
+            | ZeoxError::Spawn { argv, .. }
+            | ZeoxError::Timeout { argv, .. } => Some(argv),
+            ZeoxError::DryRun { .. } | ZeoxError::Message(_) => None,
+        }
+    }
+
+    // Full error output, for the detail view
+    pub fn details(&self) -> String {
+        match self {
+            ZeoxError::Command { stderr, .. } => stderr.clone(),
+            ZeoxError::Spawn { source, .. } => source.to_string(),
+            ZeoxError::Timeout { after, .. } => format!(
+                "zeit was still running after {}s and was killed. Another zeit may be \
+                 holding the database, raise zeit_timeout_secs if it's just slow.",
+                after.as_secs()
+            ),
+            ZeoxError::DryRun { argv } => argv.join(" "),
+            ZeoxError::Message(message) => message.clone(),
+        }
+    }
+}
+
+impl fmt::Display for ZeoxError {
+    // Only the first line, so it fits a status bar
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZeoxError::Command { argv, stderr } => {
+                let first_line = stderr.lines().find(|l| !l.trim().is_empty());
+                write!(
+                    f,
+                    "'{}' failed: {}",
+                    argv.join(" "),
+                    first_line.unwrap_or("no error output")
+                )
+            }
+            ZeoxError::Spawn { argv, source } => {
+                write!(f, "Failed to execute '{}': {}", argv.join(" "), source)
+            }
+            ZeoxError::Timeout { argv, after } => {
+                write!(
+                    f,
+                    "'{}' timed out after {}s",
+                    argv.join(" "),
+                    after.as_secs()
+                )
+            }
+            ZeoxError::DryRun { argv } => write!(f, "dry run: {}", argv.join(" ")),
+            ZeoxError::Message(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for ZeoxError {}
+
+impl From<String> for ZeoxError {
+    fn from(message: String) -> Self {
+        ZeoxError::Message(message)
+    }
+}
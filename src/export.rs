@@ -0,0 +1,167 @@
+use crate::config::Config;
+use crate::entry::{format_duration, format_money, Entry};
+use chrono::NaiveDate;
+use std::fmt::Write;
+
+// Options of 'zeox export'
+#[derive(Debug, Default)]
+pub struct Export {
+    pub format: String,
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+    // Written to stdout when not given
+    pub output: Option<String>,
+}
+
+impl Export {
+    // Entries that began within since..=until
+    pub fn select<'a>(&self, entries: &'a [Entry]) -> Vec<&'a Entry> {
+        let mut selected: Vec<&Entry> = entries
+            .iter()
+            .filter(|entry| {
+                let day = entry.begin.date_naive();
+                self.since.is_none_or(|since| day >= since)
+                    && self.until.is_none_or(|until| day <= until)
+            })
+            .collect();
+        selected.sort_by_key(|entry| entry.begin);
+        selected
+    }
+
+    // e.g. '2024-03-01 to 2024-03-31', for headings
+    pub fn period(&self) -> String {
+        match (self.since, self.until) {
+            (Some(since), Some(until)) => format!("{} to {}", since, until),
+            (Some(since), None) => format!("since {}", since),
+            (None, Some(until)) => format!("until {}", until),
+            (None, None) => "all time".to_string(),
+        }
+    }
+}
+
+// Escape what would break a Markdown table cell
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+// A table per day with its total, then the grand total. Entries spanning
+// midnight count towards the day they began.
+pub fn markdown_timesheet(entries: &[&Entry]) -> String {
+    let mut out = String::from("# Timesheet\n");
+    let mut total = 0;
+
+    for day in entries.chunk_by(|a, b| a.begin.date_naive() == b.begin.date_naive()) {
+        let date = day[0].begin.date_naive();
+        let _ = writeln!(out, "\n## {}\n", date.format("%A, %Y-%m-%d"));
+        out.push_str("| Project | Task | Begin | Finish | Duration |\n");
+        out.push_str("|---|---|---|---|---:|\n");
+
+        let mut day_total = 0;
+        for entry in day {
+            let finish = if entry.running {
+                "running".to_string()
+            } else {
+                entry.finish.format("%H:%M").to_string()
+            };
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} |",
+                cell(&entry.project),
+                cell(&entry.task),
+                entry.begin.format("%H:%M"),
+                finish,
+                format_duration(entry.minutes())
+            );
+            day_total += entry.minutes();
+        }
+
+        let _ = writeln!(
+            out,
+            "| **Total** | | | | **{}** |",
+            format_duration(day_total)
+        );
+        total += day_total;
+    }
+
+    let _ = writeln!(out, "\n**Grand total: {}**", format_duration(total));
+    out
+}
+
+fn hours(minutes: u64) -> String {
+    format!("{:.2} h", minutes as f64 / 60.0)
+}
+
+// A plain-text invoice to paste into an email: the entries grouped by
+// project with a subtotal each, then the total. Amounts show for projects
+// with a rate.
+pub fn invoice(entries: &[&Entry], period: &str, config: &Config) -> String {
+    let mut entries = entries.to_vec();
+    // Stable, so every project stays in order of begin
+    entries.sort_by(|a, b| a.project.cmp(&b.project));
+
+    let task_width = entries
+        .iter()
+        .map(|entry| entry.task.chars().count())
+        .chain([8])
+        .max()
+        .unwrap_or(8);
+    // Date, two spaces and the task
+    let label_width = 12 + task_width;
+    let amount = |project: &str, minutes: u64| {
+        config
+            .rate(project)
+            .map(|rate| {
+                format!(
+                    "  {}",
+                    format_money(minutes as f64 / 60.0 * rate, &config.currency)
+                )
+            })
+            .unwrap_or_default()
+    };
+
+    let mut out = format!("Invoice, {}\n", period);
+    let mut total = 0;
+    let mut earnings = 0.0;
+
+    for group in entries.chunk_by(|a, b| a.project == b.project) {
+        let project = &group[0].project;
+        let _ = writeln!(out, "\n{}", project);
+
+        let mut subtotal = 0;
+        for entry in group {
+            let _ = writeln!(
+                out,
+                "  {}  {:<task_width$}  {:>8}",
+                entry.begin.format("%Y-%m-%d"),
+                entry.task,
+                hours(entry.minutes())
+            );
+            subtotal += entry.minutes();
+        }
+
+        let _ = writeln!(
+            out,
+            "  {:<label_width$}  {:>8}{}",
+            "Subtotal",
+            hours(subtotal),
+            amount(project, subtotal)
+        );
+        total += subtotal;
+        earnings += config
+            .rate(project)
+            .map_or(0.0, |rate| subtotal as f64 / 60.0 * rate);
+    }
+
+    let _ = write!(
+        out,
+        "\n{:<width$}  {:>8}",
+        "Total",
+        hours(total),
+        width = label_width + 2
+    );
+    if config.has_rates() {
+        let _ = write!(out, "  {}", format_money(earnings, &config.currency));
+    }
+    let _ = writeln!(out, "  ({})", format_duration(total));
+    out
+}
@@ -0,0 +1,74 @@
+use crate::time_input::round_to_increment;
+use chrono::{DateTime, Duration, Local};
+
+// Markers snap to this many minutes when moved
+pub const SNAP_MINUTES: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Marker {
+    Begin,
+    Finish,
+}
+
+// The finish form: begin and finish of the running session as two markers on
+// a timeline, adjusted with the arrow keys
+#[derive(Debug, Clone)]
+pub struct FinishForm {
+    pub begin: DateTime<Local>,
+    pub finish: DateTime<Local>,
+    pub focus: Marker,
+    // Where the timeline starts and ends
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+}
+
+impl FinishForm {
+    pub fn new(begin: DateTime<Local>, now: DateTime<Local>) -> Self {
+        // Leave some room before the begin so it can be moved earlier
+        let padding = ((now - begin) / 4).max(Duration::minutes(30));
+
+        Self {
+            begin,
+            finish: now,
+            focus: Marker::Finish,
+            start: begin - padding,
+            end: now,
+        }
+    }
+
+    pub fn toggle_focus(&mut self) {
+        self.focus = match self.focus {
+            Marker::Begin => Marker::Finish,
+            Marker::Finish => Marker::Begin,
+        };
+    }
+
+    // Move the focused marker by whole snap steps, keeping both inside the
+    // timeline and begin before finish (max/min rather than clamp, a session
+    // younger than one step has no valid range)
+    pub fn step(&mut self, steps: i64) {
+        let delta = Duration::minutes(steps * i64::from(SNAP_MINUTES));
+        let snap = |time: DateTime<Local>| round_to_increment(time + delta, SNAP_MINUTES);
+        let step = Duration::minutes(i64::from(SNAP_MINUTES));
+
+        match self.focus {
+            Marker::Begin => {
+                self.begin = snap(self.begin).max(self.start).min(self.finish - step);
+            }
+            Marker::Finish => {
+                self.finish = snap(self.finish).min(self.end).max(self.begin + step);
+            }
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.finish - self.begin
+    }
+
+    // Column of 'time' on a timeline 'width' cells wide
+    pub fn column(&self, time: DateTime<Local>, width: u16) -> u16 {
+        let span = (self.end - self.start).num_seconds().max(1);
+        let offset = (time - self.start).num_seconds().clamp(0, span);
+        (offset * i64::from(width.saturating_sub(1)) / span) as u16
+    }
+}
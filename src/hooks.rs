@@ -0,0 +1,115 @@
+use serde::Deserialize;
+use std::{process::Command, sync::mpsc::Sender, thread};
+
+// Shell commands run on session events, e.g.
+//   session_start = "notify-send 'Started tracking' {project}"
+// {project}, {task} and {duration} are substituted shell-quoted, and also
+// exported as ZEOX_PROJECT, ZEOX_TASK and ZEOX_DURATION.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Hooks {
+    pub session_start: Option<String>,
+    pub session_finish: Option<String>,
+    pub pomodoro_break: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum HookEvent {
+    SessionStart,
+    SessionFinish,
+    PomodoroBreak,
+}
+
+impl HookEvent {
+    fn name(self) -> &'static str {
+        match self {
+            HookEvent::SessionStart => "session_start",
+            HookEvent::SessionFinish => "session_finish",
+            HookEvent::PomodoroBreak => "pomodoro_break",
+        }
+    }
+}
+
+impl Hooks {
+    fn command(&self, event: HookEvent) -> Option<&str> {
+        match event {
+            HookEvent::SessionStart => self.session_start.as_deref(),
+            HookEvent::SessionFinish => self.session_finish.as_deref(),
+            HookEvent::PomodoroBreak => self.pomodoro_break.as_deref(),
+        }
+        .filter(|command| !command.trim().is_empty())
+    }
+
+    // Run the hook for 'event' on a detached thread, failures are sent to 'log'
+    pub fn fire(&self, event: HookEvent, vars: &[(&str, String)], log: &Sender<String>) {
+        let Some(template) = self.command(event) else {
+            return;
+        };
+
+        let mut command = template.to_string();
+        for (name, value) in vars {
+            command = command.replace(&format!("{{{}}}", name), &shell_quote(value));
+        }
+
+        let env: Vec<(String, String)> = vars
+            .iter()
+            .map(|(name, value)| (format!("ZEOX_{}", name.to_uppercase()), value.clone()))
+            .collect();
+        let log = log.clone();
+
+        thread::spawn(move || {
+            let result = Command::new("sh")
+                .arg("-c")
+                .arg(&command)
+                .envs(env)
+                .output();
+
+            let message = match result {
+                Ok(output) if output.status.success() => return,
+                Ok(output) => format!(
+                    "{} hook failed ({}): {}",
+                    event.name(),
+                    output.status,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+                Err(err) => format!("{} hook could not run: {}", event.name(), err),
+            };
+            let _ = log.send(message);
+        });
+    }
+}
+
+// 'value' as a single sh word, so names like "x'; rm -rf ~" stay data. Safe
+// bare or inside single quotes, not inside double quotes.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn echoed(template: &str, value: &str) -> String {
+        let command = template.replace("{project}", &shell_quote(value));
+        let output = Command::new("sh").arg("-c").arg(command).output().unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    #[test]
+    fn values_stay_one_word() {
+        for value in [
+            "acme",
+            "x'; echo pwned #",
+            "$(echo pwned)",
+            "a b",
+            "`id`",
+            "",
+        ] {
+            assert_eq!(echoed("printf %s {project}", value), value);
+            assert_eq!(
+                echoed("printf %s 'p: '{project}", value),
+                format!("p: {}", value)
+            );
+        }
+    }
+}
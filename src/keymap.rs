@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+// Actions that can be rebound in the [keys] config table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    Start,
+    Finish,
+    List,
+    Stats,
+    Calendar,
+    Back,
+    Help,
+}
+
+impl Action {
+    pub const ALL: [Action; 8] = [
+        Action::Quit,
+        Action::Start,
+        Action::Finish,
+        Action::List,
+        Action::Stats,
+        Action::Calendar,
+        Action::Back,
+        Action::Help,
+    ];
+
+    // Name used in the config file
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Start => "start",
+            Action::Finish => "finish",
+            Action::List => "list",
+            Action::Stats => "stats",
+            Action::Calendar => "calendar",
+            Action::Back => "back",
+            Action::Help => "help",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Quit => "quit zeox",
+            Action::Start => "start tracking",
+            Action::Finish => "finish tracking",
+            Action::List => "show tracked activities",
+            Action::Stats => "show statistics",
+            Action::Calendar => "show the month calendar",
+            Action::Back => "back to the main screen",
+            Action::Help => "toggle this help",
+        }
+    }
+
+    fn default_key(self) -> char {
+        match self {
+            Action::Quit => 'q',
+            Action::Start => 's',
+            Action::Finish => 'f',
+            Action::List => 'l',
+            Action::Stats => 'd',
+            Action::Calendar => 'c',
+            Action::Back => 'b',
+            Action::Help => '?',
+        }
+    }
+}
+
+// Keys run_app handles itself, from anywhere (z ! : E and the digits) or on
+// the main screen, so an action bound to one would never fire
+const RESERVED: &[char] = &[
+    'z', '!', ':', 'E', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 't', 'n', 'e', 'u', 'R',
+    'p', 'F', 'y', 'w', 'o', 'O', 'P', 'Q', ',', '[', ']',
+];
+
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    keys: HashMap<Action, char>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self {
+            keys: Action::ALL
+                .iter()
+                .map(|&action| (action, action.default_key()))
+                .collect(),
+        }
+    }
+}
+
+impl KeyMap {
+    // Apply the [keys] overrides on top of the defaults, rejecting unknown
+    // actions, keys bound to more than one action and reserved keys
+    pub fn from_config(overrides: &HashMap<String, char>) -> Result<Self, String> {
+        let mut keymap = Self::default();
+
+        for (name, &key) in overrides {
+            let action = Action::ALL
+                .into_iter()
+                .find(|action| action.name() == name)
+                .ok_or_else(|| format!("Unknown action '{}' in [keys]", name))?;
+            keymap.keys.insert(action, key);
+        }
+
+        let mut conflicts = Vec::new();
+        for action in Action::ALL {
+            if RESERVED.contains(&keymap.key(action)) {
+                conflicts.push(format!(
+                    "'{}' for {} is reserved",
+                    keymap.key(action),
+                    action.name()
+                ));
+            }
+        }
+        for (i, &a) in Action::ALL.iter().enumerate() {
+            for &b in &Action::ALL[i + 1..] {
+                if keymap.key(a) == keymap.key(b) {
+                    conflicts.push(format!(
+                        "'{}' is bound to both {} and {}",
+                        keymap.key(a),
+                        a.name(),
+                        b.name()
+                    ));
+                }
+            }
+        }
+
+        if conflicts.is_empty() {
+            Ok(keymap)
+        } else {
+            Err(format!(
+                "Conflicting key bindings: {}",
+                conflicts.join(", ")
+            ))
+        }
+    }
+
+    pub fn key(&self, action: Action) -> char {
+        self.keys
+            .get(&action)
+            .copied()
+            .unwrap_or(action.default_key())
+    }
+
+    // Whether 'c' triggers 'action'
+    pub fn is(&self, action: Action, c: char) -> bool {
+        self.key(action) == c
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(bindings: &[(&str, char)]) -> Result<KeyMap, String> {
+        KeyMap::from_config(
+            &bindings
+                .iter()
+                .map(|&(name, key)| (name.to_string(), key))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn rebinding_to_a_free_key() {
+        let keymap = keys(&[("start", 'a')]).unwrap();
+        assert!(keymap.is(Action::Start, 'a'));
+        assert!(!keymap.is(Action::Start, 's'));
+    }
+
+    #[test]
+    fn reserved_and_conflicting_keys_are_rejected() {
+        assert_eq!(
+            keys(&[("start", 't')]).unwrap_err(),
+            "Conflicting key bindings: 't' for start is reserved"
+        );
+        assert!(keys(&[("help", '3')]).is_err());
+        assert!(keys(&[("list", 's')])
+            .unwrap_err()
+            .contains("'s' is bound to both start and list"));
+        assert!(keys(&[("nope", 'x')]).is_err());
+    }
+}
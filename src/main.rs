@@ -1,234 +1,5117 @@
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{self, Event, KeyCode, KeyEvent},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::SetTitle,
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use requestty::prompt;
 use std::{
-    io,
+    cell::Cell,
+    collections::{HashMap, HashSet},
+    env,
+    io::{self, Write},
     process::Command,
-    sync::mpsc::{self, Receiver, Sender},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
     thread,
-    time::Duration,
+    time::{Duration, Instant, SystemTime},
 };
 
+mod ascii;
+mod begin_prompt;
+mod bigtext;
+mod clipboard;
+mod colors;
+mod columns;
+mod compare;
+mod config;
+mod entry;
+mod error;
+mod export;
+mod finish_form;
+mod hooks;
+mod keymap;
+mod notify;
+mod output;
+mod palette;
+mod pomodoro;
+mod profile;
+mod runner;
+mod settings;
+mod shell;
+mod term;
+mod time_input;
+
+use begin_prompt::BeginPrompt;
+use chrono::{DateTime, Datelike, Local, Months, NaiveDate};
+use colors::ProjectColors;
+use columns::Column;
+use compare::{Comparison, Window};
+use config::{Config, StartGuard};
+use entry::{
+    day_totals, format_duration, format_money, humanize_relative, length_buckets, parse_list,
+    project_totals, push_line, recent_projects, Entry, Filter, Tracking,
+};
+use error::ZeoxError;
+use export::{invoice, markdown_timesheet, Export};
+use finish_form::{FinishForm, Marker};
+use hooks::HookEvent;
+use keymap::{Action, KeyMap};
+use output::take_lossy_warnings;
+use palette::{fuzzy_score, Palette};
+use pomodoro::PomodoroState;
+use runner::{ProcessRunner, ZeitRunner};
+use settings::Setting;
+use time_input::{parse_time_input, round_to_increment, TimeInput};
+
+// How many projects the recent-projects picker offers
+const RECENT_PICKER_SIZE: usize = 10;
+
+// Spinner frames shown in titles while zeit runs, one step per tick
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const ASCII_SPINNER: [&str; 4] = ["|", "/", "-", "\\"];
+
+// How long '[' / ']' must be left alone before the nudges are sent to zeit
+const NUDGE_DEBOUNCE: Duration = Duration::from_millis(700);
+
+// Reuse fetched output for a few seconds so hopping between screens stays snappy
+const CACHE_TTL: Duration = Duration::from_secs(5);
+
+struct CachedOutput {
+    text: String,
+    // Entries parsed from the output, only 'zeit list' has any
+    entries: Vec<Entry>,
+    // Whether the output is parsed into 'entries' line by line instead of
+    // kept as text, so years of 'zeit list' aren't held twice. Only what
+    // comes before the first entry stays in 'text', e.g. an error.
+    parses: bool,
+    fetched_at: Option<Instant>,
+    // When the fetch behind 'text' started, i.e. the zeit data it reflects.
+    // Wall clock time, to compare with the database's mtime.
+    as_of: Option<SystemTime>,
+    requested_at: Option<SystemTime>,
+    in_flight: bool,
+    // Lines streamed so far by a background fetch that shows its progress
+    partial: Option<String>,
+    // Entries of a background fetch that leaves the old ones up meanwhile
+    pending: Option<Vec<Entry>>,
+    // The zeit command behind the output, and what to call it in errors
+    args: &'static [&'static str],
+    label: &'static str,
+}
+
+// What a background fetch sends back, line by line and then whether it
+// failed, with the error to show instead
+enum Refresh {
+    Line(String),
+    Done(Option<String>),
+}
+
+// Take one line of output that 'parses': entries and their notes go into
+// 'entries', anything else into 'text' until the first entry shows up
+fn take_line(text: &mut String, entries: &mut Vec<Entry>, line: &str) {
+    push_line(entries, line);
+    if entries.is_empty() {
+        text.push_str(line);
+        text.push('\n');
+    } else if !text.is_empty() {
+        text.clear();
+    }
+}
+
+impl CachedOutput {
+    fn new(args: &'static [&'static str], label: &'static str, parses: bool) -> Self {
+        Self {
+            text: String::new(),
+            entries: Vec::new(),
+            parses,
+            fetched_at: None,
+            as_of: None,
+            requested_at: None,
+            in_flight: false,
+            partial: None,
+            pending: None,
+            args,
+            label,
+        }
+    }
+
+    fn error_text(label: &str, err: &ZeoxError) -> String {
+        format!("Error getting {}: {}", label, err.details())
+    }
+
+    // The output to draw: streamed lines while a visible refresh runs
+    fn shown_text(&self) -> &str {
+        match &self.partial {
+            Some(partial) if self.text.is_empty() => partial,
+            _ => &self.text,
+        }
+    }
+
+    fn is_stale(&self) -> bool {
+        self.fetched_at
+            .is_none_or(|fetched_at| fetched_at.elapsed() >= CACHE_TTL)
+    }
+
+    // Re-run zeit only if the cached output is missing or too old
+    fn ensure_fresh(&mut self, runner: &dyn ZeitRunner) {
+        if self.is_stale() {
+            self.refresh(runner);
+        }
+    }
+
+    fn refresh(&mut self, runner: &dyn ZeitRunner) {
+        self.requested_at = Some(SystemTime::now());
+
+        if !self.parses {
+            let text = runner
+                .run(self.args)
+                .unwrap_or_else(|err| Self::error_text(self.label, &err));
+            self.finish(text, Vec::new(), None);
+            return;
+        }
+
+        let (mut text, mut entries) = (String::new(), Vec::new());
+        let result = runner.stream(self.args, &mut |line| {
+            take_line(&mut text, &mut entries, line)
+        });
+        let error = result.err().map(|err| Self::error_text(self.label, &err));
+        self.finish(text, entries, error);
+    }
+
+    // Fetch on a worker thread, streaming lines back through 'tx'. With
+    // 'visible' the old output is dropped so the new one shows as it arrives,
+    // otherwise it stays up until the fetch is done (e.g. auto-refresh).
+    fn refresh_in_background(
+        &mut self,
+        screen: Screen,
+        tx: &Sender<(Screen, Refresh)>,
+        runner: &Arc<dyn ZeitRunner>,
+        visible: bool,
+    ) {
+        // Skip this tick while the previous fetch is still running
+        if self.in_flight {
+            return;
+        }
+
+        self.in_flight = true;
+        self.requested_at = Some(SystemTime::now());
+        self.partial = Some(String::new());
+        if visible {
+            self.text.clear();
+            self.entries.clear();
+        } else {
+            self.pending = Some(Vec::new());
+        }
+
+        let (args, label) = (self.args, self.label);
+        let tx = tx.clone();
+        let runner = Arc::clone(runner);
+        thread::spawn(move || {
+            let result = runner.stream(args, &mut |line| {
+                let _ = tx.send((screen, Refresh::Line(line.to_string())));
+            });
+            let error = result.err().map(|err| Self::error_text(label, &err));
+            let _ = tx.send((screen, Refresh::Done(error)));
+        });
+    }
+
+    fn append(&mut self, line: &str) {
+        let Some(partial) = &mut self.partial else {
+            return;
+        };
+        if !self.parses {
+            partial.push_str(line);
+            partial.push('\n');
+            return;
+        }
+
+        // A visible refresh fills the table in as the entries arrive
+        let entries = self.pending.as_mut().unwrap_or(&mut self.entries);
+        take_line(partial, entries, line);
+    }
+
+    // The background fetch is over, 'error' replaces what it streamed
+    fn done(&mut self, error: Option<String>) {
+        let text = self.partial.take().unwrap_or_default();
+        let entries = match self.pending.take() {
+            Some(entries) => entries,
+            None => std::mem::take(&mut self.entries),
+        };
+        self.finish(text, entries, error);
+    }
+
+    fn finish(&mut self, text: String, entries: Vec<Entry>, error: Option<String>) {
+        (self.text, self.entries) = match error {
+            Some(error) => (error, Vec::new()),
+            None => (text, entries),
+        };
+        self.fetched_at = Some(Instant::now());
+        self.as_of = self.requested_at.take();
+        self.in_flight = false;
+        self.partial = None;
+        self.pending = None;
+    }
+
+    // Highest sensible scroll offset, keeps the last line on screen
+    fn max_scroll(&self) -> u16 {
+        self.text.lines().count().saturating_sub(1) as u16
+    }
+
+    fn invalidate(&mut self) {
+        self.fetched_at = None;
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
 enum Screen {
     Main,
     List,
     Stats,
+    Calendar,
+    Log,
+    // List and stats side by side
+    Dashboard,
+    // Common config values, changed in place and saved to zeox.toml
+    Settings,
+    ErrorDetail,
+    // Output of a raw zeit command typed after '!'
+    Command,
+}
+
+impl Screen {
+    // Order used when cycling with Tab/Shift+Tab
+    const ALL: [Screen; 7] = [
+        Screen::Main,
+        Screen::List,
+        Screen::Stats,
+        Screen::Calendar,
+        Screen::Log,
+        Screen::Dashboard,
+        Screen::Settings,
+    ];
+
+    fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&s| s == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Screen::Main => "Main",
+            Screen::List => "List",
+            Screen::Stats => "Stats",
+            Screen::Calendar => "Calendar",
+            Screen::Log => "Log",
+            Screen::Dashboard => "Dashboard",
+            Screen::Settings => "Settings",
+            Screen::ErrorDetail => "Error",
+            Screen::Command => "zeit",
+        }
+    }
+
+    fn prev(self) -> Self {
+        let index = Self::ALL.iter().position(|&s| s == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+// An entry of the ':' palette. Running it goes to 'screen' and presses 'key',
+// so the palette can never disagree with the key bindings.
+struct PaletteCommand {
+    label: String,
+    key: Option<char>,
+    screen: Option<Screen>,
+}
+
+fn palette_commands(keys: &KeyMap) -> Vec<PaletteCommand> {
+    let command = |label: &str, key: Option<char>, screen: Option<Screen>| PaletteCommand {
+        label: label.to_string(),
+        key,
+        screen,
+    };
+
+    let mut commands: Vec<PaletteCommand> = Action::ALL
+        .iter()
+        .filter(|&&action| action != Action::Back)
+        .map(|&action| {
+            let screen = (action != Action::Help).then_some(Screen::Main);
+            command(action.description(), Some(keys.key(action)), screen)
+        })
+        .collect();
+
+    commands.extend([
+        command("show the log", None, Some(Screen::Log)),
+        command("show the dashboard", None, Some(Screen::Dashboard)),
+        command("change settings", None, Some(Screen::Settings)),
+        command("switch task", Some('t'), Some(Screen::Main)),
+        command(
+            "rename or annotate the running session",
+            Some('n'),
+            Some(Screen::Main),
+        ),
+        command("undo the last start/finish", Some('u'), Some(Screen::Main)),
+        command(
+            "continue the last finished session",
+            Some('R'),
+            Some(Screen::Main),
+        ),
+        command("start or stop a pomodoro", Some('p'), Some(Screen::Main)),
+        command("enter focus mode", Some('F'), Some(Screen::Main)),
+        command(
+            "adjust the running session's begin",
+            Some('e'),
+            Some(Screen::Main),
+        ),
+        command("copy the running session", Some('y'), Some(Screen::Main)),
+        command("switch zeit profile", Some('w'), Some(Screen::Main)),
+        command("edit zeit data in $EDITOR", Some('o'), Some(Screen::Main)),
+        command(
+            "edit the zeox config in $EDITOR",
+            Some('O'),
+            Some(Screen::Main),
+        ),
+        command("toggle dry run", Some('P'), Some(Screen::Main)),
+        command("quit without confirmation", Some('Q'), Some(Screen::Main)),
+        command("refresh the list", Some('r'), Some(Screen::List)),
+        command("delete the selected entry", Some('d'), Some(Screen::List)),
+        command("delete all filtered entries", Some('D'), Some(Screen::List)),
+        command(
+            "invoice the filtered entries",
+            Some('I'),
+            Some(Screen::List),
+        ),
+        command("go to a date in the list", Some('g'), Some(Screen::List)),
+        command(
+            "sort the list by the next column",
+            Some('o'),
+            Some(Screen::List),
+        ),
+        command("reverse the list's sort", Some('O'), Some(Screen::List)),
+        command(
+            "compare with the previous week",
+            Some('c'),
+            Some(Screen::Stats),
+        ),
+        command(
+            "compare days, weeks or months",
+            Some('w'),
+            Some(Screen::Stats),
+        ),
+        command("run a raw zeit command", Some('!'), None),
+        command("zen mode", Some('z'), None),
+    ]);
+    commands
+}
+
+// Commands matching the palette query, best match first
+fn filtered_palette_commands(keys: &KeyMap, query: &str) -> Vec<PaletteCommand> {
+    let mut matches: Vec<(usize, PaletteCommand)> = palette_commands(keys)
+        .into_iter()
+        .filter_map(|command| fuzzy_score(query, &command.label).map(|score| (score, command)))
+        .collect();
+    matches.sort_by_key(|(score, _)| *score);
+    matches.into_iter().map(|(_, command)| command).collect()
+}
+
+// Which half of the Dashboard takes the arrow keys
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Pane {
+    List,
+    Stats,
+}
+
+// What the clock is doing, at a glance
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TrackingState {
+    Idle,
+    Active,
+    // Still running, but nobody has touched a key for idle_timeout_minutes
+    Paused,
+}
+
+impl TrackingState {
+    fn glyph(self) -> &'static str {
+        match self {
+            TrackingState::Idle => "◯",
+            TrackingState::Active => "▶",
+            TrackingState::Paused => "⏸",
+        }
+    }
+
+    fn color(self) -> ratatui::style::Color {
+        use ratatui::style::Color;
+
+        match self {
+            TrackingState::Idle => Color::Gray,
+            TrackingState::Active => Color::Green,
+            TrackingState::Paused => Color::Yellow,
+        }
+    }
+}
+
+// An action waiting for a y/n in the confirmation modal
+enum Confirmation {
+    Quit,
+    Delete(Entry),
+}
+
+impl Confirmation {
+    fn question(&self) -> String {
+        match self {
+            Confirmation::Quit => "You're still tracking — quit anyway?".to_string(),
+            Confirmation::Delete(entry) => {
+                format!("Delete '{}'? This can't be undone.", describe_entry(entry))
+            }
+        }
+    }
+}
+
+// The last start/finish, with what it takes to reverse it
+enum LastAction {
+    Started { id: String },
+    Finished { entry: Entry },
 }
 
 struct App {
+    config: Config,
+    tracking_state: TrackingState,
+    // Shared with the worker threads that refresh in the background
+    runner: Arc<dyn ZeitRunner>,
+    keys: KeyMap,
     current_screen: Screen,
     tracking_status: String,
-    list_output: String,
-    stats_output: String,
+    list_output: CachedOutput,
+    stats_output: CachedOutput,
+    // Selected row of the List table
+    list_selected: usize,
+    // Ids of the entries marked with space, bulk actions work on these
+    list_marked: HashSet<String>,
+    // Task of the selected entry while it's edited inline with Enter
+    task_edit: Option<String>,
+    project_colors: ProjectColors,
+    list_columns: Vec<Column>,
+    // Digits starting a project on the main screen, from quick_projects
+    quick_projects: Vec<(char, String)>,
+    // Column the List is sorted by and whether it's reversed, None keeps
+    // zeit's order. Reset whenever the List is entered again.
+    list_sort: Option<(Column, bool)>,
+    // Draw with plain ASCII, from ascii_mode or else the terminal
+    ascii: bool,
+    // First row in view, kept by render_entries so the window only moves
+    // when the selection leaves it
+    list_offset: Cell<usize>,
+    stats_scroll: u16,
+    // Selected row of the per-project table on the Stats screen
+    stats_selected: usize,
+    // Projects with less total time are folded into an 'other' row
+    stats_min_minutes: u64,
+    // Week number and year-to-date total in the stats title, toggled with 'y';
+    // the total is fetched the first time and kept for the session
+    show_year: bool,
+    year_minutes: Option<u64>,
+    // The window compared on the Stats screen instead of the zeit stats,
+    // and what was fetched for each so far
+    comparison: Option<Window>,
+    comparisons: HashMap<Window, Comparison>,
+    // Flash 'Refreshed' once the fetches started with Home are in
+    refresh_flash: bool,
+    // Narrows down which entries the List screen shows
+    filter: Filter,
+    // Day under the cursor on the Calendar screen, its month is the one shown
+    calendar_day: NaiveDate,
+    auto_refresh_list: bool,
+    auto_refresh_stats: bool,
+    today_minutes: u64,
+    status: Option<String>,
+    last_error: Option<ZeoxError>,
+    error_scroll: u16,
+    previous_screen: Screen,
+    log: Vec<(DateTime<Local>, String)>,
+    log_scroll: u16,
+    // Selected row of the Settings screen, into Setting::ALL
+    settings_selected: usize,
+    log_tx: Sender<String>,
+    log_rx: Receiver<String>,
+    show_help: bool,
+    dry_run: bool,
+    reminded_hours: u64,
+    // Counted down as a ring on the main screen, started with 'p'
+    pomodoro: Option<PomodoroState>,
+    // Focus mode, nothing on screen but the timer until the next key
+    focus: bool,
+    // Advanced every tick, picks the spinner frame
+    spinner_frame: usize,
+    window_title: String,
+    last_activity: Instant,
+    idle_since: Option<Instant>,
+    // Minutes the session found running on launch has been going, while
+    // that's suspiciously long and the banner hasn't been answered
+    long_session: Option<u64>,
+    long_session_checked: bool,
+    // Waiting for a 'y' before going ahead, see App::confirm
+    confirming: Option<Confirmation>,
+    // Opened on the first copy and kept, X11 drops the contents with it
+    clipboard: Option<arboard::Clipboard>,
+    // Index into config.profiles
+    profile: usize,
+    // One level of undo for 'u'
+    last_action: Option<LastAction>,
+    // Banner shown above every screen until the next key
+    warning: Option<String>,
+    // Minutes to move the running session's begin by, and when that last changed
+    pending_nudge: Option<(i64, Instant)>,
+    // Last raw command run from '!' and what it printed
+    command_line: String,
+    command_output: String,
+    command_scroll: u16,
+    // Where the Command screen was opened from, ErrorDetail may take previous_screen
+    command_return: Screen,
+    // Open while the ':' command palette is shown
+    palette: Option<Palette>,
+    // Notes jotted down during the running session, attached when it finishes
+    notes: Vec<String>,
+    // Parsed auto_finish_at, and when it was last checked against the clock
+    auto_finish: Option<chrono::NaiveTime>,
+    auto_finish_checked: DateTime<Local>,
+    // Open while the finish timeline is shown
+    finish_form: Option<FinishForm>,
+    // Most recently used projects, newest first, refreshed after every finish
+    recent_projects: Vec<String>,
+    // Selected row while the recent-projects picker is open
+    project_picker: Option<usize>,
+    // Width of the Dashboard's list pane in percent, and the focused pane
+    dashboard_split: u16,
+    dashboard_focus: Pane,
 }
 
 impl App {
-    fn new() -> Self {
-        Self {
+    fn new(config: Config, keys: KeyMap) -> Self {
+        let runner = Arc::new(ProcessRunner::new(&config));
+        Self::with_runner(config, keys, runner)
+    }
+
+    fn with_runner(config: Config, keys: KeyMap, runner: Arc<dyn ZeitRunner>) -> Self {
+        let (log_tx, log_rx) = mpsc::channel();
+        let project_colors = ProjectColors::new(config.project_colors);
+        let ascii = config.ascii_mode.unwrap_or_else(ascii::limited_terminal);
+
+        // Where to land, an unknown value is reported but doesn't stop startup
+        let (startup, status) = match config.startup_screen.as_str() {
+            "main" => (Screen::Main, None),
+            "list" => (Screen::List, None),
+            "stats" => (Screen::Stats, None),
+            other => (
+                Screen::Main,
+                Some(format!(
+                    "Unknown startup_screen '{}', expected main, list or stats",
+                    other
+                )),
+            ),
+        };
+
+        let mut app = Self {
+            config,
+            tracking_state: TrackingState::Idle,
+            runner,
+            keys,
             current_screen: Screen::Main,
             tracking_status: String::new(),
-            list_output: String::new(),
-            stats_output: String::new(),
+            list_output: CachedOutput::new(&["list", "--no-colors"], "list", true),
+            stats_output: CachedOutput::new(&["stats", "--no-colors"], "stats", false),
+            list_selected: 0,
+            list_marked: HashSet::new(),
+            task_edit: None,
+            project_colors,
+            list_columns: columns::DEFAULT_COLUMNS.to_vec(),
+            quick_projects: Vec::new(),
+            list_sort: None,
+            ascii,
+            list_offset: Cell::new(0),
+            stats_scroll: 0,
+            stats_selected: 0,
+            stats_min_minutes: 0,
+            show_year: false,
+            refresh_flash: false,
+            year_minutes: None,
+            comparison: None,
+            comparisons: HashMap::new(),
+            filter: Filter::default(),
+            calendar_day: Local::now().date_naive(),
+            auto_refresh_list: false,
+            auto_refresh_stats: false,
+            today_minutes: 0,
+            status: None,
+            last_error: None,
+            error_scroll: 0,
+            previous_screen: Screen::Main,
+            log: Vec::new(),
+            log_scroll: 0,
+            settings_selected: 0,
+            log_tx,
+            log_rx,
+            show_help: false,
+            dry_run: false,
+            reminded_hours: 0,
+            pomodoro: None,
+            focus: false,
+            spinner_frame: 0,
+            window_title: String::new(),
+            last_activity: Instant::now(),
+            idle_since: None,
+            long_session: None,
+            long_session_checked: false,
+            confirming: None,
+            clipboard: None,
+            profile: 0,
+            last_action: None,
+            warning: None,
+            pending_nudge: None,
+            command_line: String::new(),
+            command_output: String::new(),
+            command_scroll: 0,
+            command_return: Screen::Main,
+            palette: None,
+            notes: Vec::new(),
+            auto_finish: None,
+            auto_finish_checked: Local::now(),
+            finish_form: None,
+            recent_projects: Vec::new(),
+            project_picker: None,
+            dashboard_split: 50,
+            dashboard_focus: Pane::List,
+        };
+
+        app.enter_screen(startup);
+        app.status = status;
+        app
+    }
+
+    fn is_tracking(&self) -> bool {
+        // 'zeit tracking' reports an active session as "▶ tracking <task> on <project> ..."
+        self.tracking_status
+            .trim_start_matches(|c: char| !c.is_alphabetic())
+            .starts_with("tracking")
+    }
+
+    fn update_tracking_state(&mut self) {
+        self.tracking_state = if !self.is_tracking() {
+            TrackingState::Idle
+        } else if self.idle_since.is_some() {
+            TrackingState::Paused
+        } else {
+            TrackingState::Active
+        };
+    }
+
+    fn tracking(&self) -> Option<Tracking> {
+        Tracking::parse(&self.tracking_status)
+    }
+
+    // Keep a bounded history of background messages for the log screen
+    fn log(&mut self, message: String) {
+        const MAX_LOG_LINES: usize = 500;
+
+        self.log.push((Local::now(), message));
+        if self.log.len() > MAX_LOG_LINES {
+            self.log.remove(0);
         }
     }
-}
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Set up terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    fn fire_hook(&self, event: HookEvent, session: Option<&Tracking>) {
+        let vars = match session {
+            Some(session) => vec![
+                ("project", session.project.clone()),
+                ("task", session.task.clone()),
+                ("duration", format_duration(session.elapsed_minutes)),
+            ],
+            None => Vec::new(),
+        };
+        self.config.hooks.fire(event, &vars, &self.log_tx);
+    }
 
-    // Create application state
-    let mut app = App::new();
+    // Wrap up a start attempt: report failures, reload, then run the start hook
+    fn after_start(&mut self, result: Result<(), ZeoxError>) {
+        let started = result.is_ok();
+        self.report(result);
+        self.after_mutation();
 
-    // Run the application
-    let res = run_app(&mut terminal, &mut app);
+        if started {
+            // A previous session may have been finished first, taking the notes along
+            self.notes.clear();
+            self.last_action = running_entry(self.runner.as_ref())
+                .ok()
+                .map(|entry| LastAction::Started { id: entry.id });
 
-    // Restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+            let session = self.tracking();
+            self.fire_hook(HookEvent::SessionStart, session.as_ref());
+            self.reminded_hours = 0;
 
-    // Handle any errors
-    if let Err(err) = res {
-        println!("Error: {:?}", err);
+            if self.config.notifications {
+                let body = session.map_or_else(String::new, |s| describe_session(&s));
+                notify::send("Started tracking", &body, &self.log_tx);
+            }
+        }
     }
 
-    Ok(())
-}
+    // Wrap up a finish attempt for the session that was running before it
+    fn after_finish(&mut self, session: Option<Tracking>, result: Result<(), ZeoxError>) {
+        if result.is_ok() {
+            self.notes.clear();
+            self.last_action = last_finished_entry(self.runner.as_ref())
+                .map(|entry| LastAction::Finished { entry });
 
-fn run_app(
-    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
-    app: &mut App,
-) -> io::Result<()> {
-    let (tx, rx): (Sender<()>, Receiver<()>) = mpsc::channel();
+            self.fire_hook(HookEvent::SessionFinish, session.as_ref());
+            self.refresh_recent_projects();
 
-    // Spawn a thread to update the tracking status every second
-    let tracking_tx = tx.clone();
-    thread::spawn(move || loop {
-        tracking_tx.send(()).unwrap();
-        thread::sleep(Duration::from_secs(1));
-    });
+            if self.config.notify_on_finish {
+                // Terminal bell, works everywhere including over SSH
+                let mut stdout = io::stdout();
+                let _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+            }
 
-    loop {
-        // Check for tracking status updates
-        while let Ok(_) = rx.try_recv() {
-            app.tracking_status = get_current_tracking();
-            if let Screen::Main = app.current_screen {
-                // Redraw the UI if we're on the main screen
-                terminal.draw(|f| ui(f, app))?;
+            if self.config.notifications {
+                let body = session.map_or_else(String::new, |s| {
+                    format!(
+                        "{} after {}",
+                        describe_session(&s),
+                        format_duration(s.elapsed_minutes)
+                    )
+                });
+                notify::send("Finished tracking", &body, &self.log_tx);
             }
         }
+        self.report(result);
+        self.after_mutation();
+        // The breadcrumb on the main screen shows what was just finished
+        self.list_output.ensure_fresh(self.runner.as_ref());
+    }
 
-        // Draw the UI
-        terminal.draw(|f| ui(f, app))?;
+    fn refresh_recent_projects(&mut self) {
+        self.recent_projects = self
+            .runner
+            .run(&["list", "--no-colors"])
+            .map(|output| recent_projects(&parse_list(&output)))
+            .unwrap_or_default();
+        self.recent_projects.truncate(RECENT_PICKER_SIZE);
+    }
 
-        // Handle input events
-        if crossterm::event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                match app.current_screen {
-                    Screen::Main => match key.code {
-                        KeyCode::Char('q') => {
-                            // Exit the application
-                            break;
-                        }
-                        KeyCode::Char('s') => {
-                            // Start tracking
-                            start_tracking();
-                            app.tracking_status = get_current_tracking();
-                        }
-                        KeyCode::Char('f') => {
-                            // Finish tracking
-                            finish_tracking();
-                            app.tracking_status = get_current_tracking();
-                        }
-                        KeyCode::Char('l') => {
-                            // Switch to list screen
-                            app.current_screen = Screen::List;
-                            app.list_output = get_list_output();
-                        }
-                        KeyCode::Char('d') => {
-                            // Switch to stats screen
-                            app.current_screen = Screen::Stats;
-                            app.stats_output = get_stats_output();
-                        }
-                        _ => {}
-                    },
-                    Screen::List | Screen::Stats => match key.code {
-                        KeyCode::Char('b') => {
-                            // Go back to main screen
-                            app.current_screen = Screen::Main;
-                        }
-                        _ => {}
-                    },
+    // List entries that pass the current filter
+    fn visible_entries(&self) -> Vec<&Entry> {
+        let mut entries: Vec<&Entry> = self
+            .list_output
+            .entries
+            .iter()
+            .filter(|entry| self.filter.matches(entry))
+            .collect();
+        // Stable, so ties stay in zeit's order
+        if let Some((column, reversed)) = self.list_sort {
+            entries.sort_by(|a, b| {
+                let order = column.compare(a, b);
+                if reversed {
+                    order.reverse()
+                } else {
+                    order
                 }
+            });
+        }
+        entries
+    }
+
+    // 'o' moves on to the next sort column, back to zeit's order after the
+    // last; 'O' flips the direction. The selected entry stays selected.
+    fn change_sort(&mut self, reverse: bool) {
+        let selected = self
+            .visible_entries()
+            .get(self.list_selected)
+            .map(|entry| entry.id.clone());
+
+        self.list_sort = match (self.list_sort, reverse) {
+            (None, true) => {
+                self.status = Some("Not sorted, o picks a column first".to_string());
+                return;
             }
+            (Some((column, reversed)), true) => Some((column, !reversed)),
+            (None, false) => Some((columns::SORTABLE[0], false)),
+            (Some((column, _)), false) => columns::SORTABLE
+                .iter()
+                .position(|&sortable| sortable == column)
+                .and_then(|index| columns::SORTABLE.get(index + 1))
+                .map(|&next| (next, false)),
+        };
+
+        self.status = Some(match self.list_sort {
+            Some((column, reversed)) => format!(
+                "Sorted by {}{}",
+                column.name(),
+                if reversed { ", reversed" } else { "" }
+            ),
+            None => "In zeit's order".to_string(),
+        });
+        if let Some(index) = selected.and_then(|id| {
+            self.visible_entries()
+                .iter()
+                .position(|entry| entry.id == id)
+        }) {
+            self.list_selected = index;
         }
     }
 
-    Ok(())
-}
+    // Per-project totals above the threshold, plus how many projects and
+    // minutes fell below it
+    fn stats_totals(&self) -> (Vec<(String, u64)>, (usize, u64)) {
+        let (shown, hidden): (Vec<_>, Vec<_>) = project_totals(&self.list_output.entries)
+            .into_iter()
+            .partition(|(_, minutes)| *minutes >= self.stats_min_minutes);
+        let other = (
+            hidden.len(),
+            hidden.iter().map(|(_, minutes)| minutes).sum(),
+        );
+        (shown, other)
+    }
 
-fn ui(f: &mut ratatui::Frame, app: &App) {
-    use ratatui::{
-        layout::{Constraint, Direction, Layout},
-        widgets::{Block, Borders, Paragraph, Wrap},
-    };
+    // Back to the top of the list and stats with fresh data, the output of
+    // the screen in view is dropped so it visibly reloads
+    fn jump_to_now(&mut self, tx: &Sender<(Screen, Refresh)>) {
+        self.list_selected = 0;
+        self.list_offset.set(0);
+        self.stats_selected = 0;
+        self.stats_scroll = 0;
 
-    let size = f.area();
+        let screen = self.current_screen;
+        self.list_output.refresh_in_background(
+            Screen::List,
+            tx,
+            &self.runner,
+            screen != Screen::Stats,
+        );
+        self.stats_output.refresh_in_background(
+            Screen::Stats,
+            tx,
+            &self.runner,
+            screen != Screen::List,
+        );
+        self.refresh_flash = true;
+    }
 
-    match app.current_screen {
-        Screen::Main => {
-            let chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .margin(1)
-                .constraints([Constraint::Min(1), Constraint::Length(3)].as_ref())
-                .split(size);
+    // Whether the zeit data changed since the List was fetched, e.g. through
+    // another zeox, so the selection may not be what it looks like. Refreshes
+    // and warns when it did, the action is left to be retried.
+    fn list_is_outdated(&mut self) -> bool {
+        let Some(as_of) = self.list_output.as_of else {
+            return false;
+        };
+        let modified = profile::active_db()
+            .and_then(|db| std::fs::metadata(db).ok())
+            .and_then(|metadata| metadata.modified().ok());
+        if modified.is_none_or(|modified| modified <= as_of) {
+            return false;
+        }
+
+        self.last_action = None;
+        self.after_mutation();
+        self.list_output.refresh(self.runner.as_ref());
+        self.clamp_list_selection();
+        self.warning = Some(
+            "The zeit data changed since the list was loaded, it's refreshed now — check the selection and try again"
+                .to_string(),
+        );
+        true
+    }
 
-            let block = Block::default().title("Zeit Tracker").borders(Borders::ALL);
+    fn clamp_list_selection(&mut self) {
+        let count = self.visible_entries().len();
+        self.list_selected = self.list_selected.min(count.saturating_sub(1));
+    }
 
-            let paragraph = Paragraph::new(app.tracking_status.clone())
-                .block(block)
-                .wrap(Wrap { trim: true });
+    // Pull every scroll offset back so a terminal 'height' rows tall still
+    // shows a full page, e.g. after it was resized
+    fn clamp_scrolls(&mut self, height: u16) {
+        // Tab bar, hint line and the borders around the content
+        let rows = height.saturating_sub(4) as usize;
+        let limit = |lines: usize| lines.saturating_sub(rows) as u16;
+
+        let error_lines = self
+            .last_error
+            .as_ref()
+            .map(|err| err.details().lines().count() + 2)
+            .unwrap_or(0);
+
+        self.stats_scroll = self
+            .stats_scroll
+            .min(limit(self.stats_output.text.lines().count()));
+        self.log_scroll = self.log_scroll.min(limit(self.log.len()));
+        self.command_scroll = self
+            .command_scroll
+            .min(limit(self.command_output.lines().count()));
+        self.error_scroll = self.error_scroll.min(limit(error_lines));
+        self.clamp_list_selection();
+    }
+
+    // Switch screens, fetching whatever the new screen shows
+    fn enter_screen(&mut self, screen: Screen) {
+        if screen == Screen::List && self.current_screen != Screen::List {
+            self.list_sort = None;
+        }
+        self.current_screen = screen;
+        match screen {
+            Screen::Log | Screen::Settings | Screen::ErrorDetail | Screen::Command => {}
+            // The main screen's breadcrumb comes from the list
+            Screen::Main | Screen::List => self.list_output.ensure_fresh(self.runner.as_ref()),
+            Screen::Calendar => self.list_output.ensure_fresh(self.runner.as_ref()),
+            Screen::Stats | Screen::Dashboard => {
+                // Per-project totals are computed from the list
+                self.list_output.ensure_fresh(self.runner.as_ref());
+                self.stats_output.ensure_fresh(self.runner.as_ref());
+            }
+        }
+    }
+
+    // Surface a failed action in the status line, keeping the full error around for 'E'
+    fn report(&mut self, result: Result<(), ZeoxError>) {
+        if let Err(ZeoxError::DryRun { argv }) = &result {
+            let command = argv.join(" ");
+            self.status = Some(format!("dry run: {}", command));
+            self.log(format!("dry run: {}", command));
+            return;
+        }
+
+        if let Err(err) = result {
+            // A hung zeit deserves more than the status line
+            if matches!(err, ZeoxError::Timeout { .. }) {
+                self.warning = Some(format!("zeit timed out: {}", err));
+            }
+            self.status = Some(if err.argv().is_some() {
+                format!("{} • E: details", err)
+            } else {
+                err.to_string()
+            });
+            self.last_error = Some(err);
+            self.error_scroll = 0;
+        }
+    }
+
+    // Reload everything a start/finish/edit may have changed
+    fn after_mutation(&mut self) {
+        self.tracking_status = get_current_tracking(self.runner.as_ref());
+        self.update_tracking_state();
+        self.today_minutes = get_today_minutes(self.runner.as_ref());
+        self.list_output.invalidate();
+        self.stats_output.invalidate();
+        self.comparisons.clear();
+    }
+
+    // Show 'window' compared to the one before, fetched once until the
+    // data changes
+    fn compare(&mut self, window: Window) {
+        if !self.comparisons.contains_key(&window) {
+            match Comparison::fetch(self.runner.as_ref(), window) {
+                Ok(comparison) => {
+                    self.comparisons.insert(window, comparison);
+                }
+                Err(err) => {
+                    self.report(Err(err));
+                    return;
+                }
+            }
+        }
+        self.comparison = Some(window);
+    }
+
+    // Once, when the first status comes in: a session running for longer
+    // than long_session_hours was likely left behind by a crash or a
+    // forgotten finish
+    fn check_long_session(&mut self) {
+        if self.long_session_checked {
+            return;
+        }
+        self.long_session_checked = true;
+
+        let threshold = self.config.long_session_hours * 60;
+        self.long_session = self
+            .tracking()
+            .map(|session| session.elapsed_minutes)
+            .filter(|&minutes| threshold > 0 && minutes >= threshold);
+    }
+
+    // Show the running session in the terminal title, e.g. "zeox — acme 01:23"
+    fn update_window_title(&mut self) -> io::Result<()> {
+        let title = match self.tracking() {
+            Some(session) => format!(
+                "zeox — {} {:02}:{:02}",
+                session.project,
+                session.elapsed_minutes / 60,
+                session.elapsed_minutes % 60
+            ),
+            None => "zeox".to_string(),
+        };
+
+        if title != self.window_title {
+            execute!(io::stdout(), SetTitle(&title))?;
+            self.window_title = title;
+        }
+
+        Ok(())
+    }
+
+    // Remind once per full hour of continuous tracking
+    fn check_reminder(&mut self) {
+        if !self.config.notifications || !self.config.hourly_reminder {
+            return;
+        }
+
+        let Some(session) = self.tracking() else {
+            self.reminded_hours = 0;
+            return;
+        };
+
+        let hours = session.elapsed_minutes / 60;
+        if hours > self.reminded_hours {
+            self.reminded_hours = hours;
+            notify::send(
+                "Still tracking",
+                &format!("{} for {}h", describe_session(&session), hours),
+                &self.log_tx,
+            );
+        }
+    }
+
+    // Start a pomodoro, or drop the running one
+    fn toggle_pomodoro(&mut self) {
+        self.pomodoro = match self.pomodoro {
+            Some(_) => {
+                self.status = Some("Pomodoro stopped".to_string());
+                None
+            }
+            None => {
+                self.status = Some(format!(
+                    "Pomodoro of {}m started",
+                    self.config.pomodoro_minutes
+                ));
+                Some(PomodoroState::new(self.config.pomodoro_minutes))
+            }
+        };
+    }
+
+    fn check_pomodoro(&mut self) {
+        if !self.pomodoro.is_some_and(|pomodoro| pomodoro.is_over()) {
+            return;
+        }
+
+        self.pomodoro = None;
+        self.status = Some("Pomodoro done, time for a break".to_string());
+        // {duration} is the pomodoro's length, not the session's
+        let mut vars = vec![(
+            "duration",
+            format_duration(self.config.pomodoro_minutes.max(1)),
+        )];
+        if let Some(session) = self.tracking() {
+            vars.push(("project", session.project));
+            vars.push(("task", session.task));
+        }
+        self.config
+            .hooks
+            .fire(HookEvent::PomodoroBreak, &vars, &self.log_tx);
+        if self.config.notifications {
+            notify::send(
+                "Pomodoro done",
+                &format!("{} minutes, time for a break", self.config.pomodoro_minutes),
+                &self.log_tx,
+            );
+        }
+    }
+
+    fn copy_to_clipboard(&mut self, text: String) {
+        self.status = Some(match clipboard::copy(&mut self.clipboard, &text) {
+            Ok(()) => format!("Copied: {}", text),
+            Err(err) => err,
+        });
+    }
+
+    // Finish the running session and continue on the same project with a new task
+    fn switch_task(&mut self) {
+        let Some(session) = self.tracking() else {
+            self.status = Some("Not tracking, nothing to switch from".to_string());
+            return;
+        };
+
+        let switch = match prompt_switch(&self.config, &session.project) {
+            Ok(switch) => switch,
+            Err(err) => {
+                self.report(Err(err));
+                return;
+            }
+        };
+
+        let finished = quick_finish_at(
+            self.runner.as_ref(),
+            switch.at.as_deref(),
+            &self.notes_arg(),
+            self.dry_run,
+        );
+        // A dry run previews the start along with the finish
+        if let Err(ZeoxError::DryRun { argv }) = finished {
+            let started = start_switched(self.runner.as_ref(), &switch, self.dry_run);
+            self.report(then_dry_run(argv, started));
+            return;
+        }
+        let failed = finished.is_err();
+        self.after_finish(Some(session), finished);
+        if failed {
+            return;
+        }
+
+        let started = start_switched(self.runner.as_ref(), &switch, self.dry_run);
+        self.after_start(started);
+    }
+
+    // Entries bulk actions work on: the marked ones, else everything shown
+    fn bulk_entries(&self) -> Vec<&Entry> {
+        if self.list_marked.is_empty() {
+            return self.visible_entries();
+        }
+        self.list_output
+            .entries
+            .iter()
+            .filter(|entry| self.list_marked.contains(&entry.id))
+            .collect()
+    }
+
+    fn list_view(&self) -> ListView<'_> {
+        ListView {
+            selected: self.list_selected,
+            marked: &self.list_marked,
+            offset: &self.list_offset,
+            colors: &self.project_colors,
+            columns: &self.list_columns,
+            sort: self.list_sort,
+            editing: self.task_edit.as_deref(),
+        }
+    }
+
+    // Save the inline task edit of the selected entry
+    fn commit_task_edit(&mut self) {
+        let Some(task) = self.task_edit.take() else {
+            return;
+        };
+        if self.list_is_outdated() {
+            return;
+        }
+        let Some(id) = self
+            .visible_entries()
+            .get(self.list_selected)
+            .map(|entry| entry.id.clone())
+        else {
+            return;
+        };
+
+        let result = rename_task(self.runner.as_ref(), &id, task.trim(), self.dry_run);
+        if result.is_ok() {
+            // Update the row in place rather than refetching the whole list
+            if let Some(entry) = self.list_output.entries.iter_mut().find(|e| e.id == id) {
+                entry.task = task.trim().to_string();
+            }
+            // Our own write doesn't make the list outdated
+            self.list_output.as_of = Some(SystemTime::now());
+            self.status = Some(format!("Task renamed to '{}'", task.trim()));
+        }
+        self.report(result);
+    }
+
+    // Turn the marked or filtered entries into an invoice for a prompted
+    // period, copied and optionally saved
+    fn export_invoice(&mut self) {
+        let entries: Vec<Entry> = self.bulk_entries().into_iter().cloned().collect();
+        if entries.is_empty() {
+            self.status = Some("Nothing to invoice".to_string());
+            return;
+        }
+
+        let first = entries.iter().map(|e| e.begin.date_naive()).min();
+        let last = entries.iter().map(|e| e.begin.date_naive()).max();
+        let export = match prompt_invoice(first, last) {
+            Ok(export) => export,
+            Err(err) => {
+                self.report(Err(err));
+                return;
+            }
+        };
+
+        let selected = export.select(&entries);
+        if selected.is_empty() {
+            self.status = Some(format!("No entries {}", export.period()));
+            return;
+        }
+        let text = invoice(&selected, &export.period(), &self.config);
+
+        if let Some(path) = &export.output {
+            if let Err(err) = std::fs::write(path, &text) {
+                self.status = Some(format!("Failed to write {}: {}", path, err));
+                return;
+            }
+        }
+        let copied = clipboard::copy(&mut self.clipboard, &text);
+        self.status = Some(match (copied, &export.output) {
+            (Ok(()), Some(path)) => format!("Invoice copied and saved to {}", path),
+            (Ok(()), None) => format!("Invoice for {} entries copied", selected.len()),
+            (Err(_), Some(path)) => format!("Invoice saved to {}", path),
+            (Err(err), None) => err,
+        });
+    }
+
+    // Flip or ask for the selected setting, then apply and save it
+    fn change_setting(&mut self) {
+        let setting = Setting::ALL[self.settings_selected];
+        if setting.is_toggle() {
+            setting.toggle(&mut self.config);
+        } else {
+            let Some(input) = prompt_setting(setting, &setting.value(&self.config)) else {
+                return;
+            };
+            if let Err(err) = setting.set(&mut self.config, &input) {
+                self.status = Some(err);
+                return;
+            }
+        }
+
+        self.apply_setting(setting);
+        self.status = Some(match settings::save(&self.config, setting) {
+            Ok(()) => format!("Saved {} = {}", setting.key(), setting.value(&self.config)),
+            Err(err) => format!("{} (changed for this session only)", err),
+        });
+    }
+
+    // Pick up a hand-edited zeox.toml, the old config stays if the new one
+    // doesn't load
+    fn reload_config(&mut self) {
+        match load_config() {
+            Ok(loaded) => {
+                self.runner = Arc::new(ProcessRunner::new(&loaded.config));
+                self.config = loaded.config;
+                self.keys = loaded.keys;
+                self.list_columns = loaded.columns;
+                self.auto_finish = loaded.auto_finish;
+                self.quick_projects = loaded.quick_projects;
+                self.apply_setting(Setting::AsciiMode);
+                self.apply_setting(Setting::ProjectColors);
+                self.status = Some("Reloaded zeox.toml".to_string());
+                self.warn_config(loaded.problems);
+            }
+            Err(err) => self.status = Some(format!("Kept the old config: {}", err)),
+        }
+    }
+
+    // Every problem to the log, and a banner pointing there
+    fn warn_config(&mut self, problems: Vec<String>) {
+        if problems.is_empty() {
+            return;
+        }
+        self.warning = Some(format!(
+            "zeox.toml: {} (defaults used, see Log)",
+            problems.join("; ")
+        ));
+        for problem in problems {
+            self.log(format!("zeox.toml: {}", problem));
+        }
+    }
+
+    // Most settings are read from the config as they're needed, these are
+    // copied at startup and have to be updated to take effect
+    fn apply_setting(&mut self, setting: Setting) {
+        match setting {
+            Setting::AsciiMode => {
+                self.ascii = self
+                    .config
+                    .ascii_mode
+                    .unwrap_or_else(ascii::limited_terminal);
+            }
+            Setting::ProjectColors => {
+                self.project_colors = ProjectColors::new(self.config.project_colors);
+            }
+            Setting::DryRun => self.dry_run = self.config.dry_run,
+            _ => {}
+        }
+    }
+
+    // Select the first entry of 'day', or of the closest earlier day that has
+    // any, and scroll it to the top
+    fn jump_to_day(&mut self, day: NaiveDate) {
+        let entries = self.visible_entries();
+        let Some(found) = entries
+            .iter()
+            .map(|entry| entry.begin.date_naive())
+            .filter(|&date| date <= day)
+            .max()
+        else {
+            self.status = Some(format!("No entries on or before {}", day));
+            return;
+        };
+        let Some(index) = entries
+            .iter()
+            .position(|entry| entry.begin.date_naive() == found)
+        else {
+            return;
+        };
+
+        self.list_selected = index;
+        self.list_offset.set(index);
+        self.status = Some(if found == day {
+            format!("Jumped to {}", day)
+        } else {
+            format!("Nothing on {}, jumped to {}", day, found)
+        });
+    }
+
+    // Split the selected entry in two, see split_entry
+    fn split_selected(&mut self) {
+        if self.list_is_outdated() {
+            return;
+        }
+        let Some(entry) = self
+            .visible_entries()
+            .get(self.list_selected)
+            .map(|&entry| entry.clone())
+        else {
+            return;
+        };
+        // The second half is tracked, which a running session would get in the way of
+        if !entry.running && self.is_tracking() {
+            self.status = Some("Finish the running session before splitting".to_string());
+            return;
+        }
+
+        match split_entry(self.runner.as_ref(), &self.config, &entry, self.dry_run) {
+            Ok(at) => {
+                self.status = Some(format!("Split '{}' at {}", entry.task, at.format("%H:%M")));
+            }
+            Err(err) => self.report(Err(err)),
+        }
+
+        // The entry changed, undo would act on stale data
+        self.last_action = None;
+        self.after_mutation();
+        self.list_output.refresh(self.runner.as_ref());
+        self.clamp_list_selection();
+    }
+
+    // Mark or unmark the entry under the cursor
+    fn toggle_marked(&mut self) {
+        let Some(id) = self
+            .visible_entries()
+            .get(self.list_selected)
+            .map(|entry| entry.id.clone())
+        else {
+            return;
+        };
+        if !self.list_marked.remove(&id) {
+            self.list_marked.insert(id);
+        }
+    }
+
+    // Whether 'confirmation' may go ahead right away. When [confirm] asks
+    // for it, it's held for the modal instead, which runs it on 'y'.
+    fn confirm(&mut self, confirmation: Confirmation) -> bool {
+        let ask = match confirmation {
+            Confirmation::Quit => self.config.confirm.quit_while_tracking,
+            Confirmation::Delete(_) => self.config.confirm.delete,
+        };
+        if ask {
+            self.confirming = Some(confirmation);
+        }
+        !ask
+    }
+
+    fn delete_selected(&mut self) {
+        if self.list_is_outdated() {
+            return;
+        }
+        let Some(entry) = self
+            .visible_entries()
+            .get(self.list_selected)
+            .map(|&entry| entry.clone())
+        else {
+            return;
+        };
+        if entry.running {
+            self.status = Some("Finish the running session before deleting it".to_string());
+            return;
+        }
+
+        if self.confirm(Confirmation::Delete(entry.clone())) {
+            self.delete_entry(&entry);
+        }
+    }
+
+    fn delete_entry(&mut self, entry: &Entry) {
+        match run_zeit(
+            self.runner.as_ref(),
+            &["erase", &entry.id, "--no-colors"],
+            self.dry_run,
+        ) {
+            Ok(_) => self.status = Some(format!("Deleted '{}'", entry.task)),
+            Err(err) => self.report(Err(err)),
+        }
+
+        self.last_action = None;
+        self.list_marked.remove(&entry.id);
+        self.after_mutation();
+        self.list_output.refresh(self.runner.as_ref());
+        self.clamp_list_selection();
+    }
+
+    // Erase the marked entries, or every entry the List filter currently shows
+    fn delete_filtered(&mut self) {
+        if self.list_is_outdated() {
+            return;
+        }
+        if !self.filter.is_active() && self.list_marked.is_empty() {
+            self.status =
+                Some("Filter the list or mark entries first, D deletes what it shows".to_string());
+            return;
+        }
+
+        let ids: Vec<String> = self
+            .bulk_entries()
+            .iter()
+            .map(|entry| entry.id.clone())
+            .collect();
+        if ids.is_empty() {
+            self.status = Some("Nothing to delete".to_string());
+            return;
+        }
+
+        if self.dry_run {
+            for id in &ids {
+                let argv = self.runner.argv(&["erase", id, "--no-colors"]);
+                self.log(format!("dry run: {}", argv.join(" ")));
+            }
+            self.status = Some(format!(
+                "dry run: would erase {} entries (see Log)",
+                ids.len()
+            ));
+            return;
+        }
+
+        match batch_delete(self.runner.as_ref(), &ids, self.config.confirm.batch_delete) {
+            Err(err) => self.report(Err(err)),
+            Ok(failures) if failures.is_empty() => {
+                self.status = Some(format!("Deleted {} entries", ids.len()));
+            }
+            Ok(failures) => {
+                self.status = Some(format!(
+                    "Deleted {} of {} entries, {} failed • E: details",
+                    ids.len() - failures.len(),
+                    ids.len(),
+                    failures.len()
+                ));
+                self.last_error = Some(ZeoxError::Message(failures.join("\n")));
+                self.error_scroll = 0;
+            }
+        }
+
+        self.last_action = None;
+        self.list_selected = 0;
+        self.list_marked.clear();
+        self.after_mutation();
+        self.list_output.refresh(self.runner.as_ref());
+    }
+
+    // Collect a '[' / ']' press, they're applied together by 'flush_nudge'
+    fn nudge_begin(&mut self, minutes: i64) {
+        if !self.is_tracking() {
+            self.status = Some("Not tracking, nothing to adjust".to_string());
+            return;
+        }
+
+        let total = self.pending_nudge.map_or(0, |(total, _)| total) + minutes;
+        self.pending_nudge = Some((total, Instant::now()));
+        self.status = Some(describe_nudge(total));
+    }
+
+    // Send the collected nudges once the keys have been left alone for a bit
+    fn flush_nudge(&mut self) {
+        let Some((minutes, last_press)) = self.pending_nudge else {
+            return;
+        };
+        if last_press.elapsed() < NUDGE_DEBOUNCE {
+            return;
+        }
+
+        self.pending_nudge = None;
+        if minutes == 0 {
+            return;
+        }
+
+        let result = nudge_running_begin(self.runner.as_ref(), minutes, self.dry_run);
+        if result.is_ok() {
+            self.status = Some(describe_nudge(minutes));
+        }
+        self.report(result);
+        self.after_mutation();
+    }
+
+    // Ask for a raw zeit command line and show its output on the Command screen
+    fn run_passthrough(&mut self) {
+        let Some(line) = prompt_zeit_command(&self.command_line) else {
+            return;
+        };
+
+        let mut args = match shell::split_args(&line) {
+            Ok(args) if args.is_empty() => return,
+            Ok(args) => args,
+            Err(err) => {
+                self.status = Some(err);
+                return;
+            }
+        };
+        if !args.iter().any(|arg| arg == "--no-colors") {
+            args.push("--no-colors".to_string());
+        }
+
+        self.command_output = match run_zeit(self.runner.as_ref(), &args, self.dry_run) {
+            Ok(output) => output,
+            Err(ZeoxError::DryRun { argv }) => format!("dry run: {}", argv.join(" ")),
+            Err(err) => err.details(),
+        };
+        self.command_line = line;
+        self.command_scroll = 0;
+
+        if self.current_screen != Screen::Command {
+            self.command_return = self.current_screen;
+            self.current_screen = Screen::Command;
+        }
+
+        // The command may have changed anything, the structured views refetch
+        self.last_action = None;
+        self.after_mutation();
+    }
+
+    // Rename the running session's task and/or jot a note, without finishing
+    fn add_note(&mut self) {
+        let Some(session) = self.tracking() else {
+            self.status = Some("Not tracking, nothing to annotate".to_string());
+            return;
+        };
+        let Some((task, note)) = prompt_note(&session.task) else {
+            return;
+        };
+
+        let mut done = Vec::new();
+        if let Some(note) = note {
+            self.notes.push(note);
+            done.push(format!(
+                "{} note{} will be attached at finish",
+                self.notes.len(),
+                if self.notes.len() == 1 { "" } else { "s" }
+            ));
+        }
+
+        if !task.is_empty() && task != session.task {
+            let result = running_entry_id(self.runner.as_ref())
+                .and_then(|id| rename_task(self.runner.as_ref(), &id, &task, self.dry_run));
+            if result.is_err() {
+                self.report(result);
+                return;
+            }
+            // Show the new task in the status right away
+            self.after_mutation();
+            done.insert(0, format!("Now on '{}'", task));
+        }
+
+        if !done.is_empty() {
+            self.status = Some(done.join(", "));
+        }
+    }
+
+    // Everything noted so far, as one --notes value
+    fn notes_arg(&self) -> String {
+        self.notes.join("\n")
+    }
+
+    // Start a quick project, asking for a task. A running session
+    // is finished first unless on_start_while_tracking is abort.
+    fn quick_start(&mut self, project: String) {
+        let session = self.tracking();
+        if session.is_some() && self.config.on_start_while_tracking == StartGuard::Abort {
+            self.status = Some("Already tracking, finish the current session first".to_string());
+            return;
+        }
+        let Some(task) = prompt_quick_task(&project) else {
+            return;
+        };
+
+        let switch = Switch {
+            project,
+            task,
+            at: None,
+        };
+
+        if session.is_some() {
+            let finished =
+                quick_finish(self.runner.as_ref(), None, &self.notes_arg(), self.dry_run);
+            // A dry run previews the start along with the finish
+            if let Err(ZeoxError::DryRun { argv }) = finished {
+                let started = start_switched(self.runner.as_ref(), &switch, self.dry_run);
+                self.report(then_dry_run(argv, started));
+                return;
+            }
+            let failed = finished.is_err();
+            self.after_finish(session, finished);
+            if failed {
+                return;
+            }
+        }
+
+        let started = start_switched(self.runner.as_ref(), &switch, self.dry_run);
+        self.after_start(started);
+    }
+
+    // Start a new session on what was finished last, to carry on with it
+    fn continue_last(&mut self) {
+        if self.is_tracking() {
+            self.status = Some("Already tracking, finish first to continue another".to_string());
+            return;
+        }
+
+        self.list_output.ensure_fresh(self.runner.as_ref());
+        let Some(last) = self
+            .list_output
+            .entries
+            .iter()
+            .filter(|entry| !entry.running)
+            .max_by_key(|entry| entry.finish)
+        else {
+            self.status = Some("Nothing finished yet to continue".to_string());
+            return;
+        };
+        let switch = Switch {
+            project: last.project.clone(),
+            task: last.task.clone(),
+            at: None,
+        };
+
+        let result = start_switched(self.runner.as_ref(), &switch, self.dry_run);
+        let started = result.is_ok();
+        self.after_start(result);
+        if started {
+            self.status = Some(format!(
+                "Continuing {}",
+                if switch.task.is_empty() {
+                    switch.project
+                } else {
+                    format!("{}/{}", switch.project, switch.task)
+                }
+            ));
+        }
+    }
+
+    // Reverse the last start or finish, once
+    fn undo(&mut self) {
+        let Some(action) = self.last_action.take() else {
+            self.status = Some("Nothing to undo".to_string());
+            return;
+        };
+
+        let (result, message) = match &action {
+            LastAction::Started { id } => (
+                undo_start(self.runner.as_ref(), id, self.dry_run),
+                "Start undone",
+            ),
+            LastAction::Finished { entry } => (
+                undo_finish(self.runner.as_ref(), entry, self.dry_run),
+                "Finish undone",
+            ),
+        };
+
+        if result.is_ok() {
+            self.status = Some(message.to_string());
+        } else if self.dry_run {
+            // Nothing happened yet, keep it undoable
+            self.last_action = Some(action);
+        }
+        self.report(result);
+        self.after_mutation();
+    }
+
+    fn profile_name(&self) -> Option<&str> {
+        self.config
+            .profiles
+            .get(self.profile)
+            .map(|profile| profile.name.as_str())
+    }
+
+    // Move on to the next configured profile and reload everything from its database
+    fn cycle_profile(&mut self) {
+        if self.config.profiles.is_empty() {
+            self.status = Some("No profiles configured".to_string());
+            return;
+        }
+
+        self.profile = (self.profile + 1) % self.config.profiles.len();
+        self.last_action = None;
+        profile::activate(&self.config.profiles[self.profile]);
+        self.filter = Filter::default();
+        self.list_selected = 0;
+        self.stats_selected = 0;
+        self.after_mutation();
+        self.status = self
+            .profile_name()
+            .map(|name| format!("Switched to profile {}", name));
+    }
+
+    // Finish the running session when the clock passes auto_finish_at. Only the
+    // moment of passing counts, so it fires once a day and leaves sessions
+    // started later that evening alone.
+    fn check_auto_finish(&mut self) {
+        let now = Local::now();
+        let previous = std::mem::replace(&mut self.auto_finish_checked, now);
+        let Some(at) = self.auto_finish else {
+            return;
+        };
+
+        let Some(cutoff) = now
+            .date_naive()
+            .and_time(at)
+            .and_local_timezone(Local)
+            .earliest()
+        else {
+            return;
+        };
+        let passed = previous < cutoff && cutoff <= now;
+        if !passed || !self.is_tracking() {
+            return;
+        }
+
+        let session = self.tracking();
+        let result = quick_finish(self.runner.as_ref(), None, &self.notes_arg(), self.dry_run);
+        if result.is_ok() {
+            self.log(format!("Auto-finished at {}", at.format("%H:%M")));
+        }
+        self.after_finish(session, result);
+    }
+
+    fn check_idle(&mut self) {
+        if self.idle_since.is_some() || !self.is_tracking() {
+            return;
+        }
+
+        if let Some(timeout) = self.config.idle_timeout_minutes {
+            if self.last_activity.elapsed() >= Duration::from_secs(timeout * 60) {
+                self.idle_since = Some(self.last_activity);
+            }
+        }
+    }
+}
+
+// The config with everything derived from it. What didn't check out is
+// replaced by its default and described in 'problems'.
+struct LoadedConfig {
+    config: Config,
+    keys: KeyMap,
+    columns: Vec<Column>,
+    auto_finish: Option<chrono::NaiveTime>,
+    quick_projects: Vec<(char, String)>,
+    problems: Vec<String>,
+}
+
+fn load_config() -> Result<LoadedConfig, Box<dyn std::error::Error>> {
+    let (config, mut problems) = Config::load()?;
+
+    let keys = KeyMap::from_config(&config.keys).unwrap_or_else(|err| {
+        problems.push(format!("keys: {}", err));
+        KeyMap::default()
+    });
+    let columns = columns::from_config(&config.list_columns).unwrap_or_else(|err| {
+        problems.push(format!("list_columns: {}", err));
+        columns::DEFAULT_COLUMNS.to_vec()
+    });
+    let auto_finish = config.auto_finish_time().unwrap_or_else(|err| {
+        problems.push(format!("auto_finish_at: {}", err));
+        None
+    });
+    let quick_projects = config.quick_projects().unwrap_or_else(|err| {
+        problems.push(format!("quick_projects: {}", err));
+        Vec::new()
+    });
+
+    Ok(LoadedConfig {
+        config,
+        keys,
+        columns,
+        auto_finish,
+        quick_projects,
+        problems,
+    })
+}
+
+// Command line flags
+#[derive(Default)]
+struct Cli {
+    dry_run: bool,
+    // Print the running session on one line and exit, for status bars
+    oneline: bool,
+    format: Option<String>,
+    // Appearance for this launch, one of Config::THEMES
+    theme: Option<String>,
+    // 'zeox export ...' writes a timesheet instead of starting the TUI
+    export: Option<Export>,
+}
+
+impl Cli {
+    fn parse() -> Result<Self, String> {
+        let mut cli = Cli::default();
+        let mut args = env::args().skip(1).peekable();
+
+        if args.peek().map(String::as_str) == Some("export") {
+            args.next();
+            cli.export = Some(Self::parse_export(args)?);
+            return Ok(cli);
+        }
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--dry-run" => cli.dry_run = true,
+                "--oneline" => cli.oneline = true,
+                "--format" => {
+                    cli.format = Some(args.next().ok_or("--format needs a template")?);
+                    cli.oneline = true;
+                }
+                "--theme" => cli.theme = Some(args.next().ok_or("--theme needs a name")?),
+                _ => return Err(format!("Unknown argument '{}'", arg)),
+            }
+        }
+
+        Ok(cli)
+    }
+
+    // e.g. 'export --format md --since 2024-10-01 --until 2024-10-31 --output october.md'
+    fn parse_export(mut args: impl Iterator<Item = String>) -> Result<Export, String> {
+        let mut export = Export {
+            format: "md".to_string(),
+            ..Export::default()
+        };
+        let date = |value: Option<String>, flag: &str| {
+            let value = value.ok_or(format!("{} needs a date", flag))?;
+            NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+                .map_err(|_| format!("{} expects YYYY-MM-DD, got '{}'", flag, value))
+        };
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" => export.format = args.next().ok_or("--format needs a format")?,
+                "--since" => export.since = Some(date(args.next(), "--since")?),
+                "--until" => export.until = Some(date(args.next(), "--until")?),
+                "--output" | "-o" => {
+                    export.output = Some(args.next().ok_or("--output needs a file")?)
+                }
+                _ => return Err(format!("Unknown export argument '{}'", arg)),
+            }
+        }
+
+        if !["md", "invoice"].contains(&export.format.as_str()) {
+            return Err(format!(
+                "Unknown export format '{}', expected md or invoice",
+                export.format
+            ));
+        }
+
+        Ok(export)
+    }
+}
+
+// Write the timesheet or invoice for 'zeox export' to the output file or stdout
+fn run_export(config: &Config, export: &Export) -> Result<(), Box<dyn std::error::Error>> {
+    let entries = parse_list(&ProcessRunner::new(config).run(&["list", "--no-colors"])?);
+    let selected = export.select(&entries);
+    let text = match export.format.as_str() {
+        "invoice" => invoice(&selected, &export.period(), config),
+        _ => markdown_timesheet(&selected),
+    };
+
+    match &export.output {
+        Some(path) => std::fs::write(path, text)?,
+        None => print!("{}", text),
+    }
+    Ok(())
+}
+
+const ONELINE_FORMAT: &str = "◉ {project}/{task} {elapsed}";
+
+// e.g. '◉ acme/report 01:23', or the idle string when nothing is tracked
+fn oneline(config: &Config, format: Option<&str>) -> String {
+    let Some(session) = Tracking::parse(&get_current_tracking(&ProcessRunner::new(config))) else {
+        return config.oneline_idle.clone();
+    };
+
+    format
+        .unwrap_or(ONELINE_FORMAT)
+        .replace("{project}", &session.project)
+        .replace("{task}", &session.task)
+        .replace(
+            "{elapsed}",
+            &format!(
+                "{:02}:{:02}",
+                session.elapsed_minutes / 60,
+                session.elapsed_minutes % 60
+            ),
+        )
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Load the config before touching the terminal so errors stay readable
+    let LoadedConfig {
+        mut config,
+        keys,
+        columns: list_columns,
+        auto_finish,
+        quick_projects,
+        problems,
+    } = load_config()?;
+    let cli = Cli::parse()?;
+
+    if let Some(theme) = &cli.theme {
+        if !config.apply_theme(theme) {
+            eprintln!(
+                "zeox: unknown theme '{}', available: {}",
+                theme,
+                Config::THEMES.join(", ")
+            );
+            std::process::exit(2);
+        }
+    }
+
+    if let Some(first) = config.profiles.first() {
+        profile::activate(first);
+    }
+
+    if let Some(export) = &cli.export {
+        return run_export(&config, export);
+    }
+
+    if cli.oneline {
+        println!("{}", oneline(&config, cli.format.as_deref()));
+        return Ok(());
+    }
+
+    // Set up terminal
+    let mut stdout = io::stdout();
+    // Push the current window title so it can be restored on exit (xterm title stack)
+    write!(stdout, "\x1b[22;0t")?;
+    let inline_notice = term::enter()?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    // Create application state
+    let mut app = App::new(config, keys);
+    app.dry_run = cli.dry_run || app.config.dry_run;
+    app.auto_finish = auto_finish;
+    app.list_columns = list_columns;
+    app.quick_projects = quick_projects;
+    app.today_minutes = get_today_minutes(app.runner.as_ref());
+    if inline_notice.is_some() {
+        app.status = inline_notice.clone();
+    }
+    app.warn_config(problems);
+
+    // Run the application
+    let res = run_app(&mut terminal, &mut app);
+
+    // Restore terminal, the same way it was set up
+    term::leave()?;
+    if let Some(notice) = inline_notice {
+        eprintln!("zeox: {}", notice);
+    }
+    terminal.show_cursor()?;
+    write!(terminal.backend_mut(), "\x1b[23;0t")?;
+    terminal.backend_mut().flush()?;
+
+    // Handle any errors
+    if let Err(err) = res {
+        println!("Error: {:?}", err);
+    }
+
+    Ok(())
+}
+
+fn run_app(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    app: &mut App,
+) -> io::Result<()> {
+    let (tx, rx): (Sender<()>, Receiver<()>) = mpsc::channel();
+    let (refresh_tx, refresh_rx) = mpsc::channel::<(Screen, Refresh)>();
+
+    // Spawn a thread to update the tracking status every second
+    let tracking_tx = tx.clone();
+    thread::spawn(move || {
+        // The receiver is gone once the app quits, which ends the thread
+        while tracking_tx.send(()).is_ok() {
+            thread::sleep(Duration::from_secs(1));
+        }
+    });
+
+    loop {
+        // Coalesce pending ticks, a backlog only needs one update
+        let mut ticked = false;
+        while rx.try_recv().is_ok() {
+            ticked = true;
+        }
+
+        if ticked {
+            app.spinner_frame = app.spinner_frame.wrapping_add(1);
+            app.tracking_status = get_current_tracking(app.runner.as_ref());
+            app.check_long_session();
+            app.check_auto_finish();
+            app.check_idle();
+            app.update_tracking_state();
+            app.check_reminder();
+            app.check_pomodoro();
+            app.update_window_title()?;
+
+            // Keep the visible list/stats current when auto-refresh is on
+            match app.current_screen {
+                Screen::List if app.auto_refresh_list => {
+                    app.list_output.refresh_in_background(
+                        Screen::List,
+                        &refresh_tx,
+                        &app.runner,
+                        false,
+                    );
+                }
+                Screen::Stats if app.auto_refresh_stats => {
+                    app.list_output.refresh_in_background(
+                        Screen::List,
+                        &refresh_tx,
+                        &app.runner,
+                        false,
+                    );
+                    app.stats_output.refresh_in_background(
+                        Screen::Stats,
+                        &refresh_tx,
+                        &app.runner,
+                        false,
+                    );
+                }
+                Screen::Dashboard => {
+                    if app.auto_refresh_list {
+                        app.list_output.refresh_in_background(
+                            Screen::List,
+                            &refresh_tx,
+                            &app.runner,
+                            false,
+                        );
+                    }
+                    if app.auto_refresh_stats {
+                        app.stats_output.refresh_in_background(
+                            Screen::Stats,
+                            &refresh_tx,
+                            &app.runner,
+                            false,
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        app.flush_nudge();
+
+        // Pick up streamed lines and finished background refreshes
+        while let Ok((screen, refresh)) = refresh_rx.try_recv() {
+            match (screen, refresh) {
+                (Screen::List, Refresh::Line(line)) => app.list_output.append(&line),
+                (Screen::Stats, Refresh::Line(line)) => app.stats_output.append(&line),
+                (Screen::List, Refresh::Done(error)) => {
+                    app.list_output.done(error);
+                    app.clamp_list_selection();
+                }
+                (Screen::Stats, Refresh::Done(error)) => {
+                    app.stats_output.done(error);
+                    app.stats_scroll = app.stats_scroll.min(app.stats_output.max_scroll());
+                }
+                _ => {}
+            }
+            if app.refresh_flash && !app.list_output.in_flight && !app.stats_output.in_flight {
+                app.refresh_flash = false;
+                app.status = Some("Refreshed".to_string());
+            }
+        }
+
+        // Collect messages from hooks and other background work
+        while let Ok(message) = app.log_rx.try_recv() {
+            app.log(message);
+        }
+
+        // Don't let mangled names pass silently, the raw bytes go to the log
+        let lossy = take_lossy_warnings();
+        if !lossy.is_empty() {
+            app.warning = Some(
+                "zeit output wasn't valid UTF-8, some names may be mangled (see Log)".to_string(),
+            );
+            for warning in lossy {
+                app.log(warning);
+            }
+        }
+
+        // Prompts drawn over the inline screen are invisible to the diff
+        if term::take_redraw() {
+            terminal.clear()?;
+        }
+
+        // Draw the UI
+        terminal.draw(|f| ui(f, app))?;
+
+        // Handle input events
+        if crossterm::event::poll(Duration::from_millis(100))? {
+            let event = event::read()?;
+            app.last_activity = Instant::now();
+
+            if let Event::Resize(_, height) = event {
+                // Offsets that fit the old size may now be past the bottom
+                app.clamp_scrolls(height);
+                terminal.clear()?;
+                continue;
+            }
+
+            if let Event::Key(mut key) = event {
+                // Any key dismisses the previous status message and warning
+                app.status = None;
+                app.warning = None;
+
+                // and leaves focus mode, without doing anything else
+                if app.focus {
+                    app.focus = false;
+                    continue;
+                }
+
+                if let Some(since) = app.idle_since {
+                    // The idle banner takes every key until it is answered
+                    match key.code {
+                        KeyCode::Char('k') => {
+                            // Keep the idle time
+                            app.idle_since = None;
+                            app.update_tracking_state();
+                        }
+                        KeyCode::Char('d') => {
+                            // Discard the idle time by finishing when activity stopped
+                            let session = app.tracking();
+                            let result = quick_finish(
+                                app.runner.as_ref(),
+                                Some(since.elapsed()),
+                                &app.notes_arg(),
+                                app.dry_run,
+                            );
+                            app.idle_since = None;
+                            app.after_finish(session, result);
+                        }
+                        KeyCode::Char('f') => {
+                            // Finish right now
+                            let session = app.tracking();
+                            let result = quick_finish(
+                                app.runner.as_ref(),
+                                None,
+                                &app.notes_arg(),
+                                app.dry_run,
+                            );
+                            app.idle_since = None;
+                            app.after_finish(session, result);
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                // The long session banner goes away with any key, 'e' and
+                // finish go on to fix the session on the main screen
+                if app.long_session.take().is_some() {
+                    let fix = match key.code {
+                        KeyCode::Char('e') => true,
+                        KeyCode::Char(c) => app.keys.is(Action::Finish, c),
+                        _ => false,
+                    };
+                    if !fix {
+                        continue;
+                    }
+                    app.enter_screen(Screen::Main);
+                }
+
+                // The confirmation modal takes the next key, anything but
+                // 'y' (or 'q' again for quitting) backs out
+                if let Some(confirmation) = app.confirming.take() {
+                    match (confirmation, key.code) {
+                        (Confirmation::Quit, KeyCode::Char('y')) => break,
+                        (Confirmation::Quit, KeyCode::Char(c)) if app.keys.is(Action::Quit, c) => {
+                            break
+                        }
+                        (Confirmation::Delete(entry), KeyCode::Char('y')) => {
+                            app.delete_entry(&entry)
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                // The palette takes typing until a command is picked, which
+                // then runs as if its key had been pressed
+                if let Some(palette) = &mut app.palette {
+                    let count = filtered_palette_commands(&app.keys, &palette.query).len();
+                    match key.code {
+                        KeyCode::Esc => {
+                            app.palette = None;
+                            continue;
+                        }
+                        KeyCode::Enter => {
+                            let picked = filtered_palette_commands(&app.keys, &palette.query)
+                                .into_iter()
+                                .nth(palette.selected);
+                            app.palette = None;
+
+                            let Some(picked) = picked else {
+                                continue;
+                            };
+                            if let Some(screen) = picked.screen {
+                                app.enter_screen(screen);
+                            }
+                            let Some(c) = picked.key else {
+                                continue;
+                            };
+                            key = KeyEvent::from(KeyCode::Char(c));
+                        }
+                        KeyCode::Up => {
+                            palette.selected = palette.selected.saturating_sub(1);
+                            continue;
+                        }
+                        KeyCode::Down => {
+                            palette.selected = (palette.selected + 1).min(count.saturating_sub(1));
+                            continue;
+                        }
+                        KeyCode::Backspace => {
+                            palette.query.pop();
+                            palette.selected = 0;
+                            continue;
+                        }
+                        KeyCode::Char(c) => {
+                            palette.query.push(c);
+                            palette.selected = 0;
+                            continue;
+                        }
+                        _ => continue,
+                    }
+                } else if key.code == KeyCode::Char(':') {
+                    app.palette = Some(Palette::default());
+                    continue;
+                }
+
+                // The inline task edit takes every key until committed or cancelled
+                if let Some(buffer) = &mut app.task_edit {
+                    match key.code {
+                        KeyCode::Esc => app.task_edit = None,
+                        KeyCode::Enter => app.commit_task_edit(),
+                        KeyCode::Backspace => {
+                            buffer.pop();
+                        }
+                        KeyCode::Char(c) => buffer.push(c),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                // The recent-projects picker takes every key until something starts
+                if let Some(selected) = app.project_picker {
+                    let count = app.recent_projects.len();
+                    match key.code {
+                        KeyCode::Esc => app.project_picker = None,
+                        KeyCode::Up | KeyCode::Char('k') => {
+                            app.project_picker = Some(selected.saturating_sub(1));
+                        }
+                        KeyCode::Down | KeyCode::Char('j') => {
+                            app.project_picker = Some((selected + 1).min(count.saturating_sub(1)));
+                        }
+                        KeyCode::Enter => {
+                            app.project_picker = None;
+                            if let Some(project) = app.recent_projects.get(selected).cloned() {
+                                let switch = Switch {
+                                    project,
+                                    task: String::new(),
+                                    at: None,
+                                };
+                                let result =
+                                    start_switched(app.runner.as_ref(), &switch, app.dry_run);
+                                app.after_start(result);
+                            }
+                        }
+                        KeyCode::Char('n') => {
+                            // Something new, through the usual prompt
+                            app.project_picker = None;
+                            let result = start_tracking(
+                                app.runner.as_ref(),
+                                &app.config,
+                                app.is_tracking(),
+                                &app.notes_arg(),
+                                app.dry_run,
+                            );
+                            app.after_start(result);
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                // The finish form takes every key until submitted or cancelled
+                if let Some(form) = &mut app.finish_form {
+                    match key.code {
+                        KeyCode::Esc => app.finish_form = None,
+                        KeyCode::Left | KeyCode::Char('h') => form.step(-1),
+                        KeyCode::Right | KeyCode::Char('l') => form.step(1),
+                        KeyCode::Tab | KeyCode::Up | KeyCode::Down => form.toggle_focus(),
+                        KeyCode::Enter => {
+                            let (begin, finish) = (form.begin, form.finish);
+                            app.finish_form = None;
+                            let session = app.tracking();
+                            let result = finish_between(
+                                app.runner.as_ref(),
+                                begin,
+                                finish,
+                                &app.notes_arg(),
+                                app.dry_run,
+                            );
+                            app.after_finish(session, result);
+                        }
+                        KeyCode::Char('t') => {
+                            // Escape hatch: type the times (and a new task name) instead
+                            app.finish_form = None;
+                            let session = app.tracking();
+                            let result = finish_tracking(
+                                app.runner.as_ref(),
+                                &app.config,
+                                &app.notes_arg(),
+                                app.dry_run,
+                            );
+                            app.after_finish(session, result);
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                // The help overlay closes on any key
+                if app.show_help {
+                    app.show_help = false;
+                    continue;
+                }
+
+                if let KeyCode::Char(c) = key.code {
+                    if app.keys.is(Action::Help, c) {
+                        app.show_help = true;
+                        continue;
+                    }
+                }
+
+                // E opens the full output of the last failed command
+                if key.code == KeyCode::Char('E')
+                    && app.last_error.is_some()
+                    && app.current_screen != Screen::ErrorDetail
+                {
+                    app.previous_screen = app.current_screen;
+                    app.current_screen = Screen::ErrorDetail;
+                    continue;
+                }
+
+                // ! runs a raw zeit command from anywhere
+                if key.code == KeyCode::Char('!') {
+                    app.run_passthrough();
+                    terminal.clear()?;
+                    continue;
+                }
+
+                // z is zen (focus) mode from anywhere, leaving it lands back here
+                if key.code == KeyCode::Char('z') {
+                    app.focus = true;
+                    continue;
+                }
+
+                // Tab cycles through the screens from anywhere
+                match key.code {
+                    KeyCode::Tab if app.current_screen == Screen::Dashboard => {
+                        // Tab moves between the panes here, shift+tab still leaves
+                        app.dashboard_focus = match app.dashboard_focus {
+                            Pane::List => Pane::Stats,
+                            Pane::Stats => Pane::List,
+                        };
+                        continue;
+                    }
+                    KeyCode::Tab => {
+                        app.enter_screen(app.current_screen.next());
+                        continue;
+                    }
+                    KeyCode::BackTab => {
+                        app.enter_screen(app.current_screen.prev());
+                        continue;
+                    }
+                    KeyCode::Char(c @ '1'..='9')
+                        if app.current_screen == Screen::Main
+                            && app.quick_projects.iter().any(|(digit, _)| *digit == c) =>
+                    {
+                        // Bound digits start their project on the main screen
+                        let project = app
+                            .quick_projects
+                            .iter()
+                            .find(|(digit, _)| *digit == c)
+                            .map(|(_, project)| project.clone())
+                            .unwrap_or_default();
+                        app.quick_start(project);
+                        continue;
+                    }
+                    KeyCode::Char(c @ '1'..='9') => {
+                        // Number keys jump straight to a tab
+                        let index = c as usize - '1' as usize;
+                        if let Some(&screen) = Screen::ALL.get(index) {
+                            app.enter_screen(screen);
+                            continue;
+                        }
+                    }
+                    _ => {}
+                }
+
+                match app.current_screen {
+                    Screen::Main => match key.code {
+                        KeyCode::Char(c) if app.keys.is(Action::Quit, c) => {
+                            // Don't walk away from a running session by accident
+                            let quit = !app.is_tracking() || app.confirm(Confirmation::Quit);
+                            if quit {
+                                break;
+                            }
+                        }
+                        KeyCode::Char('Q') => {
+                            // Quit without asking
+                            break;
+                        }
+                        KeyCode::Char(c)
+                            if app.keys.is(Action::Start, c)
+                                && app.config.recent_picker
+                                && !app.is_tracking() =>
+                        {
+                            // Offer the recent projects, fetched once and then kept by finishes
+                            if app.recent_projects.is_empty() {
+                                app.refresh_recent_projects();
+                            }
+                            if app.recent_projects.is_empty() {
+                                let result = start_tracking(
+                                    app.runner.as_ref(),
+                                    &app.config,
+                                    false,
+                                    &app.notes_arg(),
+                                    app.dry_run,
+                                );
+                                app.after_start(result);
+                            } else {
+                                app.project_picker = Some(0);
+                            }
+                        }
+                        KeyCode::Char(c) if app.keys.is(Action::Start, c) => {
+                            // Start tracking
+                            let result = start_tracking(
+                                app.runner.as_ref(),
+                                &app.config,
+                                app.is_tracking(),
+                                &app.notes_arg(),
+                                app.dry_run,
+                            );
+                            app.after_start(result);
+                        }
+                        KeyCode::Char(c) if app.keys.is(Action::Finish, c) => {
+                            // Finish tracking, adjusting begin/finish on a timeline
+                            if app.is_tracking() {
+                                match running_entry(app.runner.as_ref()) {
+                                    Ok(entry) => {
+                                        app.finish_form = Some(FinishForm::new(
+                                            entry.begin.with_timezone(&Local),
+                                            Local::now(),
+                                        ));
+                                    }
+                                    Err(err) => app.report(Err(err)),
+                                }
+                            } else {
+                                app.status = Some("Not tracking, nothing to finish".to_string());
+                            }
+                        }
+                        KeyCode::Char(c) if app.keys.is(Action::List, c) => {
+                            // Switch to list screen
+                            app.enter_screen(Screen::List);
+                        }
+                        KeyCode::Char(c) if app.keys.is(Action::Stats, c) => {
+                            // Switch to stats screen
+                            app.enter_screen(Screen::Stats);
+                        }
+                        KeyCode::Char(c) if app.keys.is(Action::Calendar, c) => {
+                            // Switch to calendar screen
+                            app.enter_screen(Screen::Calendar);
+                        }
+                        KeyCode::Char('e') => {
+                            // Correct when the running session started
+                            if app.is_tracking() {
+                                let result =
+                                    adjust_begin(app.runner.as_ref(), &app.config, app.dry_run);
+                                app.report(result);
+                                app.after_mutation();
+                            } else {
+                                app.status = Some("Not tracking, nothing to adjust".to_string());
+                            }
+                        }
+                        KeyCode::Char('y') => {
+                            // Copy the running session, e.g. for a standup note
+                            match app.tracking() {
+                                Some(session) => app.copy_to_clipboard(format!(
+                                    "{} — {}",
+                                    describe_session(&session),
+                                    format_duration(session.elapsed_minutes)
+                                )),
+                                None => {
+                                    app.status = Some("Not tracking, nothing to copy".to_string())
+                                }
+                            }
+                        }
+                        KeyCode::Char('n') => {
+                            // Jot down what just got done
+                            app.add_note();
+                        }
+                        KeyCode::Char('[') => {
+                            // Started earlier than recorded
+                            app.nudge_begin(-1);
+                        }
+                        KeyCode::Char(']') => {
+                            app.nudge_begin(1);
+                        }
+                        KeyCode::Char('t') => {
+                            // Context switch: same project, new task
+                            app.switch_task();
+                        }
+                        KeyCode::Char('p') => {
+                            app.toggle_pomodoro();
+                        }
+                        KeyCode::Char('F') => {
+                            app.focus = true;
+                        }
+                        KeyCode::Char('R') => {
+                            // Pick up where the last session left off
+                            app.continue_last();
+                        }
+                        KeyCode::Char('u') => {
+                            // Take back the last start/finish
+                            app.undo();
+                        }
+                        KeyCode::Char('w') => {
+                            // Switch to the next zeit profile
+                            app.cycle_profile();
+                        }
+                        KeyCode::Char('P') => {
+                            // Toggle previewing commands instead of running them
+                            app.dry_run = !app.dry_run;
+                        }
+                        KeyCode::Char(',') => {
+                            app.enter_screen(Screen::Settings);
+                        }
+                        KeyCode::Char('o') => {
+                            // Hand-edit the zeit database, then reload everything
+                            // (zeit keeps its database wherever ZEIT_DB points)
+                            let edited = profile::active_db()
+                                .ok_or_else(|| {
+                                    "ZEIT_DB is not set, can't locate the zeit data".to_string()
+                                })
+                                .and_then(|db| open_in_editor(db.as_ref()));
+                            if let Err(err) = edited {
+                                app.status = Some(err);
+                            }
+                            // Anything may have changed, the undo info can't be trusted
+                            app.last_action = None;
+                            terminal.clear()?;
+                            app.after_mutation();
+                        }
+                        KeyCode::Char('O') => {
+                            // Hand-edit zeox.toml, then pick it up without a restart
+                            match Config::path() {
+                                Some(path) => match open_in_editor(&path) {
+                                    Ok(()) => app.reload_config(),
+                                    Err(err) => app.status = Some(err),
+                                },
+                                None => {
+                                    app.status =
+                                        Some("Can't locate zeox.toml, HOME is not set".to_string())
+                                }
+                            }
+                            terminal.clear()?;
+                            app.after_mutation();
+                        }
+                        _ => {}
+                    },
+                    Screen::List => match key.code {
+                        KeyCode::Char(c) if app.keys.is(Action::Back, c) => {
+                            // Go back to main screen
+                            app.current_screen = Screen::Main;
+                        }
+                        KeyCode::Char('r') => {
+                            // Force a fresh 'zeit list', shown as it streams in
+                            app.list_output.refresh_in_background(
+                                Screen::List,
+                                &refresh_tx,
+                                &app.runner,
+                                true,
+                            );
+                        }
+                        KeyCode::Home => {
+                            // Top of the screen with fresh data
+                            app.jump_to_now(&refresh_tx);
+                        }
+                        KeyCode::Char('a') => {
+                            // Toggle refreshing on every tick
+                            app.auto_refresh_list = !app.auto_refresh_list;
+                        }
+                        KeyCode::Esc if !app.list_marked.is_empty() => {
+                            // Unmark everything first, the filter goes on the next esc
+                            app.list_marked.clear();
+                        }
+                        KeyCode::Esc => {
+                            // Drop the filter
+                            app.filter = Filter::default();
+                            app.list_selected = 0;
+                        }
+                        KeyCode::Enter => {
+                            // Fix the selected entry's task in place
+                            app.task_edit = app
+                                .visible_entries()
+                                .get(app.list_selected)
+                                .map(|entry| entry.task.clone());
+                        }
+                        KeyCode::Char(' ') => {
+                            app.toggle_marked();
+                            app.list_selected += 1;
+                            app.clamp_list_selection();
+                        }
+                        KeyCode::Char('A') => {
+                            // Mark everything the filter shows
+                            let ids: Vec<String> = app
+                                .visible_entries()
+                                .iter()
+                                .map(|entry| entry.id.clone())
+                                .collect();
+                            app.list_marked.extend(ids);
+                        }
+                        KeyCode::Char('I') => {
+                            app.export_invoice();
+                        }
+                        KeyCode::Char('g') => {
+                            // Go to a date instead of scrolling there
+                            if let Some(day) = prompt_day() {
+                                app.jump_to_day(day);
+                            }
+                        }
+                        KeyCode::Char('S') => {
+                            // One entry that was really two tasks
+                            app.split_selected();
+                        }
+                        KeyCode::Char('d') => {
+                            app.delete_selected();
+                        }
+                        KeyCode::Char('o') => {
+                            app.change_sort(false);
+                        }
+                        KeyCode::Char('O') => {
+                            app.change_sort(true);
+                        }
+                        KeyCode::Char('D') => {
+                            // Bulk clean-up of the marked entries, or everything matching the filter
+                            app.delete_filtered();
+                        }
+                        KeyCode::Char('y') if !app.list_marked.is_empty() => {
+                            // Copy the marked entries, one per line
+                            let lines: Vec<String> = app
+                                .bulk_entries()
+                                .iter()
+                                .map(|entry| describe_entry(entry))
+                                .collect();
+                            app.copy_to_clipboard(lines.join("\n"));
+                        }
+                        KeyCode::Char('y') => {
+                            // Copy the selected entry as a single line
+                            let line = app
+                                .visible_entries()
+                                .get(app.list_selected)
+                                .map(|entry| describe_entry(entry));
+                            if let Some(line) = line {
+                                app.copy_to_clipboard(line);
+                            }
+                        }
+                        KeyCode::Up | KeyCode::Char('k') => {
+                            app.list_selected = app.list_selected.saturating_sub(1);
+                        }
+                        KeyCode::Down | KeyCode::Char('j') => {
+                            app.list_selected += 1;
+                            app.clamp_list_selection();
+                        }
+                        _ => {}
+                    },
+                    Screen::Calendar => match key.code {
+                        KeyCode::Char(c) if app.keys.is(Action::Back, c) => {
+                            // Go back to main screen
+                            app.current_screen = Screen::Main;
+                        }
+                        KeyCode::Char('h') => {
+                            app.calendar_day = app.calendar_day - Months::new(1);
+                        }
+                        KeyCode::Char('l') => {
+                            app.calendar_day = app.calendar_day + Months::new(1);
+                        }
+                        KeyCode::Left => {
+                            app.calendar_day =
+                                app.calendar_day.pred_opt().unwrap_or(app.calendar_day);
+                        }
+                        KeyCode::Right => {
+                            app.calendar_day =
+                                app.calendar_day.succ_opt().unwrap_or(app.calendar_day);
+                        }
+                        KeyCode::Up | KeyCode::Char('k') => {
+                            app.calendar_day = app.calendar_day - chrono::Days::new(7);
+                        }
+                        KeyCode::Down | KeyCode::Char('j') => {
+                            app.calendar_day = app.calendar_day + chrono::Days::new(7);
+                        }
+                        KeyCode::Enter => {
+                            // Show the entries of the day under the cursor
+                            app.filter = Filter {
+                                project: None,
+                                date: Some(app.calendar_day),
+                            };
+                            app.list_selected = 0;
+                            app.enter_screen(Screen::List);
+                        }
+                        _ => {}
+                    },
+                    Screen::Settings => match key.code {
+                        KeyCode::Char(c) if app.keys.is(Action::Back, c) => {
+                            // Go back to main screen
+                            app.current_screen = Screen::Main;
+                        }
+                        KeyCode::Esc => {
+                            app.current_screen = Screen::Main;
+                        }
+                        KeyCode::Up | KeyCode::Char('k') => {
+                            app.settings_selected = app.settings_selected.saturating_sub(1);
+                        }
+                        KeyCode::Down | KeyCode::Char('j') => {
+                            app.settings_selected =
+                                (app.settings_selected + 1).min(Setting::ALL.len() - 1);
+                        }
+                        KeyCode::Enter | KeyCode::Char(' ') => {
+                            app.change_setting();
+                        }
+                        _ => {}
+                    },
+                    Screen::Log => match key.code {
+                        KeyCode::Char(c) if app.keys.is(Action::Back, c) => {
+                            // Go back to main screen
+                            app.current_screen = Screen::Main;
+                        }
+                        KeyCode::Up | KeyCode::Char('k') => {
+                            app.log_scroll = app.log_scroll.saturating_sub(1);
+                        }
+                        KeyCode::Down | KeyCode::Char('j') => {
+                            let max = app.log.len().saturating_sub(1) as u16;
+                            app.log_scroll = (app.log_scroll + 1).min(max);
+                        }
+                        _ => {}
+                    },
+                    Screen::Command => match key.code {
+                        KeyCode::Esc => {
+                            app.current_screen = app.command_return;
+                        }
+                        KeyCode::Char(c) if app.keys.is(Action::Back, c) => {
+                            app.current_screen = app.command_return;
+                        }
+                        KeyCode::Up | KeyCode::Char('k') => {
+                            app.command_scroll = app.command_scroll.saturating_sub(1);
+                        }
+                        KeyCode::Down | KeyCode::Char('j') => {
+                            let max = app.command_output.lines().count().saturating_sub(1) as u16;
+                            app.command_scroll = (app.command_scroll + 1).min(max);
+                        }
+                        _ => {}
+                    },
+                    Screen::ErrorDetail => match key.code {
+                        KeyCode::Esc => {
+                            // Dismiss the error and go back to where it happened
+                            app.last_error = None;
+                            app.current_screen = app.previous_screen;
+                        }
+                        KeyCode::Char(c) if app.keys.is(Action::Back, c) => {
+                            app.last_error = None;
+                            app.current_screen = app.previous_screen;
+                        }
+                        KeyCode::Up | KeyCode::Char('k') => {
+                            app.error_scroll = app.error_scroll.saturating_sub(1);
+                        }
+                        KeyCode::Down | KeyCode::Char('j') => {
+                            app.error_scroll = app.error_scroll.saturating_add(1);
+                        }
+                        _ => {}
+                    },
+                    Screen::Dashboard => match key.code {
+                        KeyCode::Char(c) if app.keys.is(Action::Back, c) => {
+                            // Go back to main screen
+                            app.current_screen = Screen::Main;
+                        }
+                        KeyCode::Char('r') => {
+                            app.list_output.refresh_in_background(
+                                Screen::List,
+                                &refresh_tx,
+                                &app.runner,
+                                true,
+                            );
+                            app.stats_output.refresh_in_background(
+                                Screen::Stats,
+                                &refresh_tx,
+                                &app.runner,
+                                true,
+                            );
+                        }
+                        KeyCode::Home => {
+                            // Top of the screen with fresh data
+                            app.jump_to_now(&refresh_tx);
+                        }
+                        KeyCode::Char('<') => {
+                            app.dashboard_split = app.dashboard_split.saturating_sub(10).max(20);
+                        }
+                        KeyCode::Char('>') => {
+                            app.dashboard_split = (app.dashboard_split + 10).min(80);
+                        }
+                        KeyCode::Up | KeyCode::Char('k') => match app.dashboard_focus {
+                            Pane::List => {
+                                app.list_selected = app.list_selected.saturating_sub(1);
+                            }
+                            Pane::Stats => {
+                                app.stats_scroll = app.stats_scroll.saturating_sub(1);
+                            }
+                        },
+                        KeyCode::Down | KeyCode::Char('j') => match app.dashboard_focus {
+                            Pane::List => {
+                                app.list_selected += 1;
+                                app.clamp_list_selection();
+                            }
+                            Pane::Stats => {
+                                app.stats_scroll =
+                                    (app.stats_scroll + 1).min(app.stats_output.max_scroll());
+                            }
+                        },
+                        _ => {}
+                    },
+                    Screen::Stats => match key.code {
+                        KeyCode::Char(c) if app.keys.is(Action::Back, c) => {
+                            // Go back to main screen
+                            app.current_screen = Screen::Main;
+                        }
+                        KeyCode::Char('r') => {
+                            // Force a fresh 'zeit stats' and the list behind the totals
+                            app.list_output.refresh_in_background(
+                                Screen::List,
+                                &refresh_tx,
+                                &app.runner,
+                                false,
+                            );
+                            app.stats_output.refresh_in_background(
+                                Screen::Stats,
+                                &refresh_tx,
+                                &app.runner,
+                                true,
+                            );
+                            app.comparisons.clear();
+                            if let Some(window) = app.comparison {
+                                app.compare(window);
+                            }
+                        }
+                        KeyCode::Home => {
+                            // Top of the screen with fresh data
+                            app.jump_to_now(&refresh_tx);
+                        }
+                        KeyCode::Char('a') => {
+                            // Toggle refreshing on every tick
+                            app.auto_refresh_stats = !app.auto_refresh_stats;
+                        }
+                        KeyCode::Char('c') => {
+                            // Compare against the previous window instead of the zeit stats
+                            match app.comparison {
+                                Some(_) => app.comparison = None,
+                                None => app.compare(Window::Week),
+                            }
+                        }
+                        KeyCode::Char('w') => {
+                            let window = app.comparison.map_or(Window::Week, Window::next);
+                            app.compare(window);
+                        }
+                        KeyCode::Up | KeyCode::Char('k') => {
+                            app.stats_selected = app.stats_selected.saturating_sub(1);
+                        }
+                        KeyCode::Down | KeyCode::Char('j') => {
+                            let count = app.stats_totals().0.len();
+                            app.stats_selected =
+                                (app.stats_selected + 1).min(count.saturating_sub(1));
+                        }
+                        KeyCode::PageUp => {
+                            app.stats_scroll = app.stats_scroll.saturating_sub(1);
+                        }
+                        KeyCode::PageDown => {
+                            app.stats_scroll =
+                                (app.stats_scroll + 1).min(app.stats_output.max_scroll());
+                        }
+                        KeyCode::Char('+') => {
+                            // Hide more small projects
+                            app.stats_min_minutes += 5;
+                            let count = app.stats_totals().0.len();
+                            app.stats_selected = app.stats_selected.min(count.saturating_sub(1));
+                        }
+                        KeyCode::Char('-') => {
+                            app.stats_min_minutes = app.stats_min_minutes.saturating_sub(5);
+                        }
+                        KeyCode::Char('y') => {
+                            // Week and year-to-date, the total only fetched once
+                            app.show_year = !app.show_year;
+                            if app.show_year && app.year_minutes.is_none() {
+                                app.year_minutes = Some(get_year_minutes(app.runner.as_ref()));
+                            }
+                        }
+                        KeyCode::Enter => {
+                            // Drill down into the selected project's entries
+                            let (totals, _) = app.stats_totals();
+                            if let Some((project, _)) = totals.get(app.stats_selected) {
+                                app.filter = Filter {
+                                    project: Some(project.clone()),
+                                    date: None,
+                                };
+                                app.list_selected = 0;
+                                app.enter_screen(Screen::List);
+                            }
+                        }
+                        _ => {}
+                    },
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn ui(f: &mut ratatui::Frame, app: &App) {
+    use ratatui::{
+        layout::{Constraint, Direction, Layout},
+        style::{Color, Modifier, Style},
+        text::{Line, Text},
+        widgets::{Block, Borders, Paragraph, Tabs, Wrap},
+    };
+
+    if app.focus {
+        render_focus(f, app);
+        if app.ascii {
+            ascii::asciify(f.buffer_mut());
+        }
+        return;
+    }
+
+    let size = f.area();
+
+    let outer = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)].as_ref())
+        .split(size);
+
+    // Tab bar across the top
+    // Overlay screens keep the tab they were opened from highlighted
+    let tab = match app.current_screen {
+        Screen::ErrorDetail => app.previous_screen,
+        Screen::Command => app.command_return,
+        screen => screen,
+    };
+    let selected = Screen::ALL.iter().position(|&s| s == tab).unwrap_or(0);
+    let tabs = Tabs::new(
+        Screen::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, screen)| format!("{} {}", i + 1, screen.title())),
+    )
+    .select(selected)
+    .highlight_style(Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED));
+
+    f.render_widget(tabs, outer[0]);
+
+    let mut body = outer[1];
+
+    if let Some(warning) = &app.warning {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(1)].as_ref())
+            .split(body);
+        let banner = Paragraph::new(format!(" ⚠ {}", warning))
+            .style(Style::default().fg(Color::Black).bg(Color::Yellow));
+        f.render_widget(banner, rows[0]);
+        body = rows[1];
+    }
+
+    match app.current_screen {
+        Screen::Main => {
+            let gauge_height = if app.config.daily_goal_hours.is_some() {
+                1
+            } else {
+                0
+            };
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(1)
+                .constraints(
+                    [
+                        Constraint::Min(1),
+                        Constraint::Length(gauge_height),
+                        Constraint::Length(3),
+                    ]
+                    .as_ref(),
+                )
+                .split(body);
+
+            let state = app.tracking_state;
+            let mut title = format!("{} Zeit Tracker", state.glyph());
+            if let Some(name) = app.profile_name() {
+                title.push_str(&format!(" — {}", name));
+            }
+            if let Some(at) = app.auto_finish {
+                title.push_str(&format!(" — auto-finish {}", at.format("%H:%M")));
+            }
+            if app.dry_run {
+                title.push_str(" [dry run]");
+            }
+            let block = Block::default()
+                .title(title)
+                .title_style(
+                    Style::default()
+                        .fg(state.color())
+                        .add_modifier(Modifier::BOLD),
+                )
+                .title(live_indicator(app).right_aligned())
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(state.color()));
+
+            let mut status = Text::raw(app.tracking_status.clone());
+            if let Some(breadcrumb) = breadcrumb(&app.list_output.entries) {
+                status.lines.push(Line::raw(""));
+                status.lines.push(Line::styled(
+                    breadcrumb,
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            let paragraph = Paragraph::new(status)
+                .block(block)
+                .wrap(Wrap { trim: true });
+
+            // The countdown ring sits right of the session while a pomodoro runs
+            match app.pomodoro {
+                Some(pomodoro) if chunks[0].width > 40 => {
+                    let columns = Layout::default()
+                        .direction(Direction::Horizontal)
+                        .constraints([Constraint::Min(1), Constraint::Length(22)].as_ref())
+                        .split(chunks[0]);
+                    f.render_widget(paragraph, columns[0]);
+                    render_pomodoro(f, columns[1], &pomodoro, app.ascii);
+                }
+                _ => f.render_widget(paragraph, chunks[0]),
+            }
+
+            let mut lines = Vec::new();
+            if let Some(status) = &app.status {
+                lines.push(Line::styled(
+                    status.clone(),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            lines.push(hint_line(&main_hints(app), chunks[2].width, &app.keys));
+
+            let instructions = Paragraph::new(lines).wrap(Wrap { trim: true });
+
+            if let Some(goal) = app.config.daily_goal_hours {
+                render_goal_gauge(f, chunks[1], app.today_minutes, goal);
+            }
+
+            f.render_widget(instructions, chunks[2]);
+        }
+        Screen::List => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+                .split(body);
+
+            let block = Block::default()
+                .title(list_title(app))
+                .borders(Borders::ALL);
+
+            if let Some(message) = list_empty_state(app) {
+                render_empty_state(f, chunks[0], block, &message);
+            } else if app.list_output.entries.is_empty() {
+                // Nothing parseable (errors, an unknown zeit format), show it verbatim
+                let paragraph = Paragraph::new(app.list_output.shown_text().to_string())
+                    .block(block)
+                    .wrap(Wrap { trim: true });
+
+                f.render_widget(paragraph, chunks[0]);
+            } else {
+                // Table on top, the selected entry in full below
+                let panes = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(3), Constraint::Length(7)].as_ref())
+                    .split(chunks[0]);
+
+                let entries = app.visible_entries();
+                render_entries(f, panes[0], &entries, &app.list_view(), block);
+                render_entry_detail(f, panes[1], entries.get(app.list_selected).copied());
+            }
+
+            let mut hints = screen_hints(&app.keys, app.auto_refresh_list);
+            hints.push(hint('y', "copy"));
+            hints.push(hint("enter", "edit task"));
+            hints.push(hint("space", "mark"));
+            if !app.list_marked.is_empty() {
+                hints.push(hint('D', "delete marked"));
+                hints.push(hint("esc", "unmark all"));
+            } else if app.filter.is_active() {
+                hints.push(hint('A', "mark all"));
+                hints.push(hint('D', "delete shown"));
+                hints.push(hint("esc", "clear filter"));
+            }
+            // A status message (e.g. after copying) takes the hint line until the next key
+            let instructions = match &app.status {
+                Some(status) => Paragraph::new(Line::styled(
+                    status.clone(),
+                    Style::default().fg(Color::Yellow),
+                )),
+                None => Paragraph::new(hint_line(&hints, chunks[1].width, &app.keys)),
+            };
+
+            f.render_widget(instructions, chunks[1]);
+        }
+        Screen::Stats => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+                .split(body);
+
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+                .split(chunks[0]);
+
+            if app.list_output.entries.is_empty()
+                && app.stats_output.text.trim().is_empty()
+                && !app.stats_output.in_flight
+            {
+                let block = Block::default()
+                    .title(stats_title(app))
+                    .borders(Borders::ALL);
+                render_empty_state(
+                    f,
+                    chunks[0],
+                    block,
+                    &[
+                        "Nothing tracked yet".to_string(),
+                        format!(
+                            "Press {} on the main screen to start tracking",
+                            app.keys.key(Action::Start)
+                        ),
+                    ],
+                );
+            } else {
+                // Session lengths below the projects, when there's room for both
+                if columns[0].height >= 20 {
+                    let left = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints([Constraint::Min(8), Constraint::Length(9)].as_ref())
+                        .split(columns[0]);
+                    render_project_totals(f, left[0], app);
+                    render_session_lengths(f, left[1], &app.list_output.entries);
+                } else {
+                    render_project_totals(f, columns[0], app);
+                }
+
+                let comparison = app
+                    .comparison
+                    .and_then(|window| app.comparisons.get(&window));
+                if let Some(comparison) = comparison {
+                    render_comparison(f, columns[1], comparison, &app.project_colors);
+                } else {
+                    let block = Block::default()
+                        .title(stats_title(app))
+                        .borders(Borders::ALL);
+
+                    let paragraph = Paragraph::new(app.stats_output.shown_text().to_string())
+                        .block(block)
+                        .wrap(Wrap { trim: true })
+                        .scroll((app.stats_scroll, 0));
+
+                    f.render_widget(paragraph, columns[1]);
+                }
+            }
+
+            let mut hints = screen_hints(&app.keys, app.auto_refresh_stats);
+            hints.push(hint("enter", "show entries"));
+            hints.push(hint("+/-", "min duration"));
+            hints.push(hint('y', "week/YTD"));
+            hints.push(hint('c', "compare"));
+            if app.comparison.is_some() {
+                hints.push(hint('w', "day/week/month"));
+            }
+            hints.push(hint("pgup/pgdn", "scroll stats"));
+            let instructions = Paragraph::new(hint_line(&hints, chunks[1].width, &app.keys));
+
+            f.render_widget(instructions, chunks[1]);
+        }
+        Screen::Dashboard => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+                .split(body);
+
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(
+                    [
+                        Constraint::Percentage(app.dashboard_split),
+                        Constraint::Percentage(100 - app.dashboard_split),
+                    ]
+                    .as_ref(),
+                )
+                .split(chunks[0]);
+
+            // The focused pane gets a highlighted border
+            let pane_style = |pane: Pane| {
+                if app.dashboard_focus == pane {
+                    Style::default().fg(Color::Cyan)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                }
+            };
+
+            let list_block = Block::default()
+                .title(list_title(app))
+                .borders(Borders::ALL)
+                .border_style(pane_style(Pane::List));
+
+            if let Some(message) = list_empty_state(app) {
+                render_empty_state(f, columns[0], list_block, &message);
+            } else if app.list_output.entries.is_empty() {
+                let paragraph = Paragraph::new(app.list_output.shown_text().to_string())
+                    .block(list_block)
+                    .wrap(Wrap { trim: true });
+
+                f.render_widget(paragraph, columns[0]);
+            } else {
+                render_entries(
+                    f,
+                    columns[0],
+                    &app.visible_entries(),
+                    &app.list_view(),
+                    list_block,
+                );
+            }
+
+            let stats_block = Block::default()
+                .title(stats_title(app))
+                .borders(Borders::ALL)
+                .border_style(pane_style(Pane::Stats));
+
+            let paragraph = Paragraph::new(app.stats_output.shown_text().to_string())
+                .block(stats_block)
+                .wrap(Wrap { trim: true })
+                .scroll((app.stats_scroll, 0));
+
+            f.render_widget(paragraph, columns[1]);
+
+            let hints = [
+                hint(app.keys.key(Action::Back), "back"),
+                hint('r', "refresh"),
+                hint("tab", "switch pane"),
+                hint("</>", "resize"),
+                hint("↑↓", "scroll"),
+            ];
+            let instructions = match &app.status {
+                Some(status) => Paragraph::new(Line::styled(
+                    status.clone(),
+                    Style::default().fg(Color::Yellow),
+                )),
+                None => Paragraph::new(hint_line(&hints, chunks[1].width, &app.keys)),
+            };
+
+            f.render_widget(instructions, chunks[1]);
+        }
+        Screen::Calendar => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+                .split(body);
+
+            render_calendar(
+                f,
+                chunks[0],
+                app.calendar_day,
+                &day_totals(&app.list_output.entries),
+            );
+
+            let hints = [
+                hint(app.keys.key(Action::Back), "back"),
+                hint("←→", "day"),
+                hint("h/l", "month"),
+                hint("↑↓", "week"),
+                hint("enter", "show entries"),
+            ];
+            let instructions = Paragraph::new(hint_line(&hints, chunks[1].width, &app.keys));
+
+            f.render_widget(instructions, chunks[1]);
+        }
+        Screen::Settings => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+                .split(body);
+
+            render_settings(f, chunks[0], app);
+
+            let hints = [
+                hint(app.keys.key(Action::Back), "back"),
+                hint("↑↓", "select"),
+                hint("enter", "toggle/edit"),
+            ];
+            let instructions = Paragraph::new(hint_line(&hints, chunks[1].width, &app.keys));
+
+            f.render_widget(instructions, chunks[1]);
+        }
+        Screen::Log => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+                .split(body);
+
+            // Newest messages first
+            let lines: Vec<Line> = app
+                .log
+                .iter()
+                .rev()
+                .map(|(time, message)| {
+                    Line::raw(format!("{} {}", time.format("%H:%M:%S"), message))
+                })
+                .collect();
+
+            let block = Block::default()
+                .title(format!("Log — {} messages", app.log.len()))
+                .borders(Borders::ALL);
+
+            let paragraph = Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: true })
+                .scroll((app.log_scroll, 0));
+
+            f.render_widget(paragraph, chunks[0]);
+
+            let hints = [
+                hint(app.keys.key(Action::Back), "back"),
+                hint("↑↓", "scroll"),
+            ];
+            let instructions = Paragraph::new(hint_line(&hints, chunks[1].width, &app.keys));
+
+            f.render_widget(instructions, chunks[1]);
+        }
+        Screen::Command => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+                .split(body);
+
+            // Shown verbatim, nothing here is parsed
+            let block = Block::default()
+                .title(format!("zeit {}", app.command_line))
+                .borders(Borders::ALL);
+
+            let paragraph = Paragraph::new(app.command_output.clone())
+                .block(block)
+                .wrap(Wrap { trim: false })
+                .scroll((app.command_scroll, 0));
+
+            f.render_widget(paragraph, chunks[0]);
+
+            let hints = [
+                hint(format!("{}/esc", app.keys.key(Action::Back)), "back"),
+                hint('!', "run another"),
+                hint("↑↓", "scroll"),
+            ];
+            let instructions = Paragraph::new(hint_line(&hints, chunks[1].width, &app.keys));
+
+            f.render_widget(instructions, chunks[1]);
+        }
+        Screen::ErrorDetail => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+                .split(body);
+
+            let mut lines = Vec::new();
+            if let Some(err) = &app.last_error {
+                if let Some(argv) = err.argv() {
+                    lines.push(Line::styled(
+                        format!("$ {}", argv.join(" ")),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ));
+                    lines.push(Line::raw(""));
+                }
+                lines.extend(err.details().lines().map(|l| Line::raw(l.to_string())));
+            }
+
+            let block = Block::default()
+                .title("Command Error")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red));
+
+            let paragraph = Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false })
+                .scroll((app.error_scroll, 0));
+
+            f.render_widget(paragraph, chunks[0]);
+
+            let hints = [
+                hint(format!("{}/esc", app.keys.key(Action::Back)), "dismiss"),
+                hint("↑↓", "scroll"),
+            ];
+            let instructions = Paragraph::new(hint_line(&hints, chunks[1].width, &app.keys));
+
+            f.render_widget(instructions, chunks[1]);
+        }
+    }
+
+    if app.show_help {
+        render_help(f, app.current_screen, &app.keys, &app.quick_projects);
+    }
+
+    if let Some(palette) = &app.palette {
+        render_palette(f, palette, &app.keys);
+    }
+
+    if let Some(form) = &app.finish_form {
+        render_finish_form(f, form);
+    }
+
+    if let Some(selected) = app.project_picker {
+        render_project_picker(f, &app.recent_projects, selected);
+    }
+
+    if let Some(since) = app.idle_since {
+        render_idle_banner(f, since);
+    }
+
+    if let Some(minutes) = app.long_session {
+        render_long_session_banner(f, minutes, &app.keys);
+    }
+
+    if let Some(confirmation) = &app.confirming {
+        render_confirm(f, confirmation);
+    }
+
+    render_running_total(f, app);
+
+    if app.ascii {
+        ascii::asciify(f.buffer_mut());
+    }
+}
+
+// Keys that do something on 'screen', rebindable actions as they're bound
+fn screen_keys(screen: Screen, keys: &KeyMap) -> Vec<(String, &'static str)> {
+    let action = |action: Action| (keys.key(action).to_string(), action.description());
+    let key = |key: &str, description| (key.to_string(), description);
+    let back = action(Action::Back);
+
+    match screen {
+        Screen::Main => vec![
+            action(Action::Start),
+            action(Action::Finish),
+            action(Action::List),
+            action(Action::Stats),
+            action(Action::Calendar),
+            key("e", "adjust the running session's begin"),
+            key("[ ]", "move the running begin by a minute"),
+            key("t", "finish and continue with a new task"),
+            key("n", "rename the running task or add a note"),
+            key("u", "undo the last start/finish"),
+            key("R", "continue the last finished session"),
+            key("p", "start/stop a pomodoro"),
+            key("F", "focus mode, any key leaves it"),
+            key("y", "copy the running session"),
+            key("w", "switch zeit profile"),
+            key("P", "toggle dry run"),
+            key("o", "edit zeit data in $EDITOR"),
+            key("O", "edit zeox.toml in $EDITOR"),
+            key(",", "settings"),
+            action(Action::Quit),
+            key("Q", "quit without confirmation"),
+        ],
+        Screen::List => vec![
+            back,
+            key("↑↓", "select an entry"),
+            key("g", "go to a date"),
+            key("enter", "edit the selected task"),
+            key("space", "mark an entry"),
+            key("A", "mark all shown"),
+            key("esc", "unmark, then clear the filter"),
+            key("y", "copy the selected or marked entries"),
+            key("d", "delete the selected entry"),
+            key("D", "delete marked or all filtered entries"),
+            key("o", "sort by project, begin, duration or not"),
+            key("O", "reverse the sort"),
+            key("S", "split the selected entry in two"),
+            key("I", "invoice marked or filtered entries"),
+            key("r", "refresh"),
+            key("home", "back to the top and refresh"),
+            key("a", "toggle auto-refresh"),
+        ],
+        Screen::Stats => vec![
+            back,
+            key("↑↓", "select a project"),
+            key("enter", "show the project's entries"),
+            key("pgup/pgdn", "scroll the zeit stats"),
+            key("+/-", "hide small projects"),
+            key("y", "toggle week and year-to-date"),
+            key("c", "compare with the previous week"),
+            key("w", "compare days, weeks or months"),
+            key("r", "refresh"),
+            key("home", "back to the top and refresh"),
+            key("a", "toggle auto-refresh"),
+        ],
+        Screen::Calendar => vec![
+            back,
+            key("←→", "previous/next day"),
+            key("↑↓", "previous/next week"),
+            key("h/l", "previous/next month"),
+            key("enter", "show the day's entries"),
+        ],
+        Screen::Log => vec![back, key("↑↓", "scroll")],
+        Screen::Dashboard => vec![
+            back,
+            key("tab", "switch the focused pane"),
+            key("↑↓", "move in the focused pane"),
+            key("< >", "resize the split"),
+            key("r", "refresh"),
+            key("home", "back to the top and refresh"),
+        ],
+        Screen::Settings => vec![
+            back,
+            key("↑↓", "select a setting"),
+            key("enter", "toggle or edit it"),
+        ],
+        Screen::ErrorDetail => vec![key("esc", "dismiss the error"), key("↑↓", "scroll")],
+        Screen::Command => vec![key("esc", "close"), key("↑↓", "scroll")],
+    }
+}
+
+// Only the keys of the current screen, then the ones that work everywhere,
+// over a dimmed screen
+fn render_help(
+    f: &mut ratatui::Frame,
+    screen: Screen,
+    keys: &KeyMap,
+    quick_projects: &[(char, String)],
+) {
+    use ratatui::{
+        layout::Rect,
+        style::{Color, Modifier, Style},
+        text::Line,
+        widgets::{Block, Borders, Clear, Paragraph},
+    };
+
+    let everywhere = [
+        (
+            keys.key(Action::Help).to_string(),
+            Action::Help.description(),
+        ),
+        ("tab".to_string(), "next screen, shift-tab previous"),
+        ("1-9".to_string(), "jump to screen"),
+        (":".to_string(), "command palette"),
+        ("E".to_string(), "show last error"),
+        ("!".to_string(), "run a raw zeit command"),
+        ("z".to_string(), "zen mode, only the timer"),
+    ];
+    let line =
+        |(key, description): &(String, &str)| Line::raw(format!(" {:<9} {}", key, description));
+    let heading = |text: &str| {
+        Line::styled(
+            format!(" {}", text),
+            Style::default().add_modifier(Modifier::BOLD),
+        )
+    };
+
+    let mut lines = vec![heading(screen.title())];
+    lines.extend(screen_keys(screen, keys).iter().map(line));
+    if screen == Screen::Main && !quick_projects.is_empty() {
+        lines.push(Line::raw(""));
+        lines.push(heading("Quick projects"));
+        lines.extend(
+            quick_projects
+                .iter()
+                .map(|(digit, project)| Line::raw(format!(" {:<9} start {}", digit, project))),
+        );
+    }
+    lines.push(Line::raw(""));
+    lines.push(heading("Everywhere"));
+    lines.extend(everywhere.iter().map(line));
+
+    let size = f.area();
+    let width = 52.min(size.width);
+    let height = (lines.len() as u16 + 2).min(size.height);
+    let area = Rect::new(
+        size.x + (size.width - width) / 2,
+        size.y + (size.height - height) / 2,
+        width,
+        height,
+    );
+
+    let block = Block::default().title("Help").borders(Borders::ALL);
+
+    // Push the screen behind into the background
+    f.buffer_mut().set_style(
+        size,
+        Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn render_finish_form(f: &mut ratatui::Frame, form: &FinishForm) {
+    use ratatui::{
+        layout::Rect,
+        style::{Color, Modifier, Style},
+        text::{Line, Span},
+        widgets::{Block, Borders, Clear, Paragraph},
+    };
+
+    let size = f.area();
+    let width = 64.min(size.width);
+    let height = 9.min(size.height);
+    let area = Rect::new(
+        size.x + (size.width - width) / 2,
+        size.y + (size.height - height) / 2,
+        width,
+        height,
+    );
+
+    // The session as a bar between the two markers
+    let bar_width = width.saturating_sub(4);
+    let begin_column = form.column(form.begin, bar_width);
+    let finish_column = form.column(form.finish, bar_width);
+    let focused = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let bar: Vec<Span> = (0..bar_width)
+        .map(|column| {
+            if column == begin_column {
+                Span::styled(
+                    "[",
+                    if form.focus == Marker::Begin {
+                        focused
+                    } else {
+                        Style::default()
+                    },
+                )
+            } else if column == finish_column {
+                Span::styled(
+                    "]",
+                    if form.focus == Marker::Finish {
+                        focused
+                    } else {
+                        Style::default()
+                    },
+                )
+            } else if column > begin_column && column < finish_column {
+                Span::styled("━", Style::default().fg(Color::Green))
+            } else {
+                Span::styled("─", Style::default().fg(Color::DarkGray))
+            }
+        })
+        .collect();
+
+    let axis_gap = (bar_width as usize).saturating_sub(10);
+    let lines = vec![
+        Line::from(bar),
+        Line::raw(format!(
+            "{}{:>gap$}",
+            form.start.format("%H:%M"),
+            form.end.format("%H:%M"),
+            gap = axis_gap + 5
+        ))
+        .style(Style::default().fg(Color::DarkGray)),
+        Line::raw(""),
+        Line::raw(format!(
+            "begin {}   finish {}   duration {}",
+            form.begin.format("%H:%M"),
+            form.finish.format("%H:%M"),
+            format_duration(form.duration().num_minutes().max(0) as u64)
+        )),
+        Line::raw(""),
+        Line::styled(
+            "←→ move • tab switch • enter finish • t type times • esc cancel",
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
+
+    let block = Block::default().title("Finish").borders(Borders::ALL);
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+// The editable settings with their current values and where they're saved
+fn render_settings(f: &mut ratatui::Frame, area: ratatui::layout::Rect, app: &App) {
+    use ratatui::{
+        layout::Constraint,
+        style::{Color, Modifier, Style},
+        widgets::{Block, Borders, Cell, Row, Table, TableState},
+    };
+
+    let rows: Vec<Row> = Setting::ALL
+        .iter()
+        .map(|&setting| {
+            Row::new(vec![
+                Cell::from(setting.key()),
+                Cell::from(setting.value(&app.config)).style(Style::default().fg(Color::Cyan)),
+                Cell::from(setting.description()).style(Style::default().fg(Color::DarkGray)),
+            ])
+        })
+        .collect();
+
+    let path = Config::path()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|| "zeox.toml".to_string());
+    let block = Block::default()
+        .title(format!("Settings — {}", path))
+        .borders(Borders::ALL);
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(22),
+            Constraint::Length(8),
+            Constraint::Min(10),
+        ],
+    )
+    .block(block)
+    .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = TableState::default().with_selected(Some(app.settings_selected));
+
+    f.render_stateful_widget(table, area, &mut state);
+}
+
+fn render_project_picker(f: &mut ratatui::Frame, projects: &[String], selected: usize) {
+    use ratatui::{
+        layout::Rect,
+        style::{Color, Modifier, Style},
+        text::Line,
+        widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    };
+
+    let size = f.area();
+    let width = 40.min(size.width);
+    let height = (projects.len() as u16 + 2).min(size.height);
+    let area = Rect::new(
+        size.x + (size.width - width) / 2,
+        size.y + (size.height - height) / 3,
+        width,
+        height,
+    );
+
+    let items: Vec<ListItem> = projects
+        .iter()
+        .map(|project| ListItem::new(Line::raw(format!(" {}", project))))
+        .collect();
+    let block = Block::default()
+        .title("Start — recent projects")
+        .title_bottom(Line::styled(
+            " enter start • n new • esc cancel ",
+            Style::default().fg(Color::DarkGray),
+        ))
+        .borders(Borders::ALL);
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default().with_selected(Some(selected));
+
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(list, area, &mut state);
+}
+
+fn render_palette(f: &mut ratatui::Frame, palette: &Palette, keys: &KeyMap) {
+    use ratatui::{
+        layout::{Constraint, Direction, Layout, Rect},
+        style::{Modifier, Style},
+        text::Line,
+        widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    };
+
+    let commands = filtered_palette_commands(keys, &palette.query);
+
+    let size = f.area();
+    let width = 52.min(size.width);
+    let height = 14.min(size.height);
+    let area = Rect::new(
+        size.x + (size.width - width) / 2,
+        size.y + (size.height - height) / 3,
+        width,
+        height,
+    );
+
+    let block = Block::default().title("Commands").borders(Borders::ALL);
+    let inner = block.inner(area);
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)].as_ref())
+        .split(inner);
+
+    let items: Vec<ListItem> = commands
+        .iter()
+        .map(|command| {
+            let key = command.key.map_or(String::new(), |key| key.to_string());
+            ListItem::new(Line::raw(format!(" {:<3} {}", key, command.label)))
+        })
+        .collect();
+    let list = List::new(items).highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default().with_selected(Some(palette.selected));
+
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+    f.render_widget(
+        Paragraph::new(format!(": {}", palette.query))
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+        rows[0],
+    );
+    f.render_stateful_widget(list, rows[1], &mut state);
+}
+
+// Only offer what makes sense right now, e.g. finish only while tracking
+fn main_hints(app: &App) -> Vec<(String, String)> {
+    let keys = &app.keys;
+    let mut hints = vec![hint(keys.key(Action::Quit), "quit")];
+
+    if app.is_tracking() {
+        hints.push(hint(keys.key(Action::Finish), "finish"));
+        hints.push(hint('t', "switch task"));
+        hints.push(hint(
+            'n',
+            &match app.notes.len() {
+                0 => "note".to_string(),
+                count => format!("note ({})", count),
+            },
+        ));
+        hints.push(hint('e', "edit begin"));
+        hints.push(hint("[/]", "begin ∓1m"));
+        hints.push(hint('y', "copy"));
+    } else {
+        hints.push(hint(keys.key(Action::Start), "start"));
+        hints.push(hint('R', "continue last"));
+    }
+
+    if app.last_action.is_some() {
+        hints.push(hint('u', "undo"));
+    }
+
+    hints.extend([
+        hint(keys.key(Action::List), "list"),
+        hint(keys.key(Action::Stats), "stats"),
+        hint(keys.key(Action::Calendar), "calendar"),
+        hint('o', "edit data"),
+    ]);
+    if app.config.profiles.len() > 1 {
+        hints.push(hint('w', "profile"));
+    }
+    hints.extend([hint("tab", "next"), hint(keys.key(Action::Help), "help")]);
+    hints
+}
+
+fn screen_hints(keys: &KeyMap, auto_refresh: bool) -> Vec<(String, String)> {
+    vec![
+        hint(keys.key(Action::Back), "back"),
+        hint('r', "refresh"),
+        hint(
+            'a',
+            if auto_refresh {
+                "auto-refresh (on)"
+            } else {
+                "auto-refresh (off)"
+            },
+        ),
+        hint("↑↓", "scroll"),
+    ]
+}
+
+fn hint(key: impl ToString, label: &str) -> (String, String) {
+    (key.to_string(), label.to_string())
+}
+
+// Lay out key hints for the given width: 'q: quit • s: start' when it fits,
+// just the keys when it doesn't, and a pointer to the help overlay as a last resort
+fn hint_line(
+    hints: &[(String, String)],
+    width: u16,
+    keys: &KeyMap,
+) -> ratatui::text::Line<'static> {
+    use ratatui::{
+        style::{Color, Modifier, Style},
+        text::{Line, Span},
+    };
+
+    let width = width as usize;
+    let separator = Span::styled(" • ", Style::default().fg(Color::DarkGray));
+    let key_style = Style::default().add_modifier(Modifier::BOLD);
+
+    let full_width: usize = hints
+        .iter()
+        .map(|(key, label)| key.chars().count() + 2 + label.chars().count())
+        .sum::<usize>()
+        + 3 * hints.len().saturating_sub(1);
+
+    if full_width <= width {
+        let mut spans = Vec::new();
+        for (i, (key, label)) in hints.iter().enumerate() {
+            if i > 0 {
+                spans.push(separator.clone());
+            }
+            spans.push(Span::styled(key.clone(), key_style));
+            spans.push(Span::raw(format!(": {}", label)));
+        }
+        return Line::from(spans);
+    }
+
+    let compact_width: usize = hints
+        .iter()
+        .map(|(key, _)| key.chars().count())
+        .sum::<usize>()
+        + hints.len().saturating_sub(1);
+
+    if compact_width <= width {
+        let mut spans = Vec::new();
+        for (i, (key, _)) in hints.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw(" "));
+            }
+            spans.push(Span::styled(key.clone(), key_style));
+        }
+        return Line::from(spans);
+    }
+
+    Line::styled(
+        format!("press {} for help", keys.key(Action::Help)),
+        Style::default().fg(Color::DarkGray),
+    )
+}
+
+// e.g. 'Tracked Activities — project:acme date:2024-10-03 — 14 entries'
+fn list_title(app: &App) -> String {
+    let mut parts = vec!["Tracked Activities".to_string()];
+    if app.filter.is_active() {
+        parts.push(app.filter.describe());
+    }
+    if !app.list_output.entries.is_empty() {
+        let count = app.visible_entries().len();
+        parts.push(format!(
+            "{} entr{}",
+            count,
+            if count == 1 { "y" } else { "ies" }
+        ));
+    }
+    if !app.list_marked.is_empty() {
+        parts.push(format!("{} marked", app.list_marked.len()));
+    }
+    if app.list_output.in_flight {
+        parts.push(format!("{} running", spinner(app)));
+    }
+    if app.auto_refresh_list {
+        parts.push("live".to_string());
+    }
+    parts.join(" — ")
+}
+
+// The current spinner frame, plain ASCII in ascii_mode
+fn spinner(app: &App) -> &'static str {
+    let frames: &[&str] = if app.ascii { &ASCII_SPINNER } else { &SPINNER };
+    frames[app.spinner_frame % frames.len()]
+}
+
+// e.g. 'Statistics — 4 projects, 12h 30m'
+fn stats_title(app: &App) -> String {
+    let totals = project_totals(&app.list_output.entries);
+    let mut parts = vec!["Statistics".to_string()];
+    if !totals.is_empty() {
+        let minutes: u64 = totals.iter().map(|(_, minutes)| minutes).sum();
+        parts.push(format!(
+            "{} project{}, {}",
+            totals.len(),
+            if totals.len() == 1 { "" } else { "s" },
+            format_duration(minutes)
+        ));
+        if app.config.has_rates() {
+            parts.push(format!(
+                "≈ {}",
+                format_money(app.config.earnings(&totals), &app.config.currency)
+            ));
+        }
+    }
+    if app.show_year {
+        let week = Local::now().iso_week().week();
+        parts.push(match app.year_minutes {
+            Some(minutes) => format!("Week {} · YTD {}", week, format_duration(minutes)),
+            None => format!("Week {}", week),
+        });
+    }
+    if app.stats_output.in_flight {
+        parts.push(format!("{} running", spinner(app)));
+    }
+    if app.auto_refresh_stats {
+        parts.push("live".to_string());
+    }
+    parts.join(" — ")
+}
+
+// The last few finished sessions, e.g. 'recent: acme/docs (1h) · mail (20m)',
+// None without any
+fn breadcrumb(entries: &[Entry]) -> Option<String> {
+    const LENGTH: usize = 3;
+
+    let mut finished: Vec<&Entry> = entries.iter().filter(|entry| !entry.running).collect();
+    if finished.is_empty() {
+        return None;
+    }
+    finished.sort_by_key(|entry| std::cmp::Reverse(entry.finish));
+
+    let crumbs: Vec<String> = finished
+        .iter()
+        .take(LENGTH)
+        .map(|entry| {
+            let name = if entry.task.is_empty() {
+                entry.project.clone()
+            } else {
+                format!("{}/{}", entry.project, entry.task)
+            };
+            format!("{} ({})", name, format_duration(entry.minutes()))
+        })
+        .collect();
+    Some(format!("recent: {}", crumbs.join(" · ")))
+}
+
+// 'task on project', or just the project when there is no task
+fn describe_session(session: &Tracking) -> String {
+    if session.task.is_empty() {
+        session.project.clone()
+    } else {
+        format!("{} on {}", session.task, session.project)
+    }
+}
+
+// e.g. '2024-10-03 09:00–10:30 acme: write report (1h 30m)'
+fn describe_entry(entry: &Entry) -> String {
+    let finish = if entry.running {
+        "now".to_string()
+    } else {
+        entry.finish.format("%H:%M").to_string()
+    };
+    format!(
+        "{}–{} {}: {} ({})",
+        entry.begin.format("%Y-%m-%d %H:%M"),
+        finish,
+        entry.project,
+        entry.task,
+        format_duration(entry.minutes())
+    )
+}
+
+// How the List table is drawn beyond its entries
+struct ListView<'a> {
+    selected: usize,
+    marked: &'a HashSet<String>,
+    offset: &'a Cell<usize>,
+    colors: &'a ProjectColors,
+    columns: &'a [Column],
+    sort: Option<(Column, bool)>,
+    // Task typed so far while editing the selected row
+    editing: Option<&'a str>,
+}
+
+fn render_entries(
+    f: &mut ratatui::Frame,
+    area: ratatui::layout::Rect,
+    entries: &[&Entry],
+    view: &ListView,
+    block: ratatui::widgets::Block,
+) {
+    use ratatui::{
+        style::{Modifier, Style},
+        widgets::{Cell, Row, Table, TableState},
+    };
+
+    // Only build rows for what fits between header and footer, so years of
+    // entries cost no more to draw than a screenful
+    let selected = view.selected;
+    let height = (area.height.saturating_sub(4) as usize).max(1);
+    let mut start = view.offset.get().min(entries.len().saturating_sub(1));
+    if selected < start {
+        start = selected;
+    } else if selected >= start + height {
+        start = selected + 1 - height;
+    }
+    view.offset.set(start);
+    let window = &entries[start.min(entries.len())..(start + height).min(entries.len())];
+
+    let rows = window.iter().enumerate().map(|(i, entry)| {
+        let index = start + i;
+        let finish = if entry.running {
+            "running".to_string()
+        } else {
+            entry.finish.format("%H:%M").to_string()
+        };
+
+        let project = if view.marked.contains(&entry.id) {
+            format!("● {}", entry.project)
+        } else {
+            entry.project.clone()
+        };
+
+        // The task being edited inline, with a cursor
+        let task = match view.editing {
+            Some(buffer) if index == selected => format!("{}▏", buffer),
+            _ => entry.task.clone(),
+        };
+
+        let cells: Vec<Cell> = view
+            .columns
+            .iter()
+            .map(|column| match column {
+                Column::Project => {
+                    let cell = Cell::from(project.clone());
+                    match view.colors.get(&entry.project) {
+                        Some(color) => cell.style(Style::default().fg(color)),
+                        None => cell,
+                    }
+                }
+                Column::Task => Cell::from(task.clone()),
+                Column::Begin => Cell::from(entry.begin.format("%Y-%m-%d %H:%M").to_string()),
+                Column::End => Cell::from(finish.clone()),
+                Column::Duration => Cell::from(format_duration(entry.minutes())),
+                Column::Ago => {
+                    Cell::from(humanize_relative(entry.begin.with_timezone(&chrono::Utc)))
+                }
+                // First line only, the detail pane has the rest
+                Column::Note => Cell::from(entry.notes.lines().next().unwrap_or("").to_string()),
+                Column::Tags => Cell::from(entry.tags().join(" ")),
+            })
+            .collect();
+
+        Row::new(cells)
+    });
+
+    // Sum of what's visible, so it follows the filter
+    let total = entries.iter().fold(chrono::Duration::zero(), |sum, entry| {
+        sum + entry.duration()
+    });
+    // The label goes in the first column that isn't the duration
+    let label_column = view
+        .columns
+        .iter()
+        .position(|&column| column != Column::Duration);
+    let footer = Row::new(view.columns.iter().enumerate().map(|(i, &column)| {
+        if column == Column::Duration {
+            format_duration(total.num_minutes() as u64)
+        } else if Some(i) == label_column {
+            format!(
+                "Total ({} entr{})",
+                entries.len(),
+                if entries.len() == 1 { "y" } else { "ies" }
+            )
+        } else {
+            String::new()
+        }
+    }))
+    .style(Style::default().add_modifier(Modifier::BOLD));
+
+    // The sorted column's header gets an arrow pointing the way it goes
+    let header = view.columns.iter().map(|&column| match view.sort {
+        Some((sorted, reversed)) if sorted == column => {
+            format!("{} {}", column.header(), if reversed { "▼" } else { "▲" })
+        }
+        _ => column.header().to_string(),
+    });
+
+    let table = Table::new(rows, view.columns.iter().map(|column| column.width()))
+        .header(Row::new(header).style(Style::default().add_modifier(Modifier::UNDERLINED)))
+        .footer(footer)
+        .block(block)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    let mut state = TableState::default().with_selected(Some(selected - start));
+    f.render_stateful_widget(table, area, &mut state);
+}
+
+// What to say instead of an empty table, None when there's something to show
+fn list_empty_state(app: &App) -> Option<[String; 2]> {
+    if app.list_output.in_flight {
+        None
+    } else if app.list_output.entries.is_empty() && app.list_output.text.trim().is_empty() {
+        Some([
+            "No entries yet".to_string(),
+            format!(
+                "Press {} on the main screen to start tracking",
+                app.keys.key(Action::Start)
+            ),
+        ])
+    } else if !app.list_output.entries.is_empty() && app.visible_entries().is_empty() {
+        Some([
+            format!("No entries match {}", app.filter.describe()),
+            "Press esc to clear the filter".to_string(),
+        ])
+    } else {
+        None
+    }
+}
+
+// Muted lines in the middle of an otherwise empty box
+fn render_empty_state(
+    f: &mut ratatui::Frame,
+    area: ratatui::layout::Rect,
+    block: ratatui::widgets::Block,
+    message: &[String],
+) {
+    use ratatui::{
+        layout::Alignment,
+        style::{Color, Style},
+        text::Line,
+        widgets::{Paragraph, Wrap},
+    };
+
+    let padding = (area.height.saturating_sub(2) as usize).saturating_sub(message.len()) / 2;
+    let lines: Vec<Line> = std::iter::repeat_n(Line::raw(""), padding)
+        .chain(message.iter().map(|line| Line::raw(line.clone())))
+        .collect();
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(Color::DarkGray))
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(paragraph, area);
+}
+
+fn render_entry_detail(f: &mut ratatui::Frame, area: ratatui::layout::Rect, entry: Option<&Entry>) {
+    use ratatui::{
+        style::{Color, Modifier, Style},
+        text::Line,
+        widgets::{Block, Borders, Paragraph, Wrap},
+    };
+
+    let mut lines = Vec::new();
+    if let Some(entry) = entry {
+        lines.push(Line::styled(
+            describe_entry(entry),
+            Style::default().add_modifier(Modifier::BOLD),
+        ));
+        if entry.notes.is_empty() {
+            lines.push(Line::styled(
+                "No notes",
+                Style::default().fg(Color::DarkGray),
+            ));
+        } else {
+            lines.extend(entry.notes.lines().map(|l| Line::raw(l.to_string())));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().title("Details").borders(Borders::ALL))
+        .wrap(Wrap { trim: false });
+
+    f.render_widget(paragraph, area);
+}
+
+fn render_project_totals(f: &mut ratatui::Frame, area: ratatui::layout::Rect, app: &App) {
+    use ratatui::{
+        layout::Constraint,
+        style::{Color, Modifier, Style},
+        widgets::{Block, Borders, Cell, Row, Table, TableState},
+    };
+
+    let (totals, (other_count, other_minutes)) = app.stats_totals();
+    let totals = &totals[..];
+    let (min_minutes, selected) = (app.stats_min_minutes, app.stats_selected);
+
+    // With rates configured: the config and what the projects folded into 'other' earned
+    let rates = app.config.has_rates().then(|| {
+        let all = app
+            .config
+            .earnings(&project_totals(&app.list_output.entries));
+        (&app.config, all - app.config.earnings(totals))
+    });
+
+    let money = |amount: f64| match rates {
+        Some((config, _)) => format_money(amount, &config.currency),
+        None => String::new(),
+    };
+    let project_earnings = |project: &str, minutes: u64| match rates {
+        Some((config, _)) => config
+            .rate(project)
+            .map(|rate| money(minutes as f64 / 60.0 * rate))
+            .unwrap_or_else(|| "—".to_string()),
+        None => String::new(),
+    };
+
+    let mut rows: Vec<Row> = totals
+        .iter()
+        .map(|(project, minutes)| {
+            let mut name = Cell::from(project.clone());
+            if let Some(color) = app.project_colors.get(project) {
+                name = name.style(Style::default().fg(color));
+            }
+            Row::new(vec![
+                name,
+                Cell::from(format_duration(*minutes)),
+                Cell::from(project_earnings(project, *minutes)),
+            ])
+        })
+        .collect();
+
+    let other_earnings = rates.map(|(_, other)| other).unwrap_or(0.0);
+
+    // Everything under the threshold, summed up so the total still adds up
+    if other_count > 0 {
+        rows.push(
+            Row::new(vec![
+                format!("other ({})", other_count),
+                format_duration(other_minutes),
+                money(other_earnings),
+            ])
+            .style(Style::default().fg(Color::DarkGray)),
+        );
+    }
+
+    // Grand total across everything listed
+    let total: u64 = totals.iter().map(|(_, minutes)| minutes).sum::<u64>() + other_minutes;
+    let earned = rates
+        .map(|(config, other)| config.earnings(totals) + other)
+        .unwrap_or(0.0);
+    let projects = totals.len() + other_count;
+    let footer = Row::new(vec![
+        format!(
+            "Total ({} project{})",
+            projects,
+            if projects == 1 { "" } else { "s" }
+        ),
+        format_duration(total),
+        money(earned),
+    ])
+    .style(Style::default().add_modifier(Modifier::BOLD));
+
+    let title = if min_minutes > 0 {
+        format!("Projects — at least {}m", min_minutes)
+    } else {
+        "Projects".to_string()
+    };
+
+    let earnings_width = if rates.is_some() { 12 } else { 0 };
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(10),
+            Constraint::Length(10),
+            Constraint::Length(earnings_width),
+        ],
+    )
+    .header(
+        Row::new(vec!["Project", "Duration", "Earnings"])
+            .style(Style::default().add_modifier(Modifier::UNDERLINED)),
+    )
+    .footer(footer)
+    .block(Block::default().title(title).borders(Borders::ALL))
+    .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    let mut state = TableState::default().with_selected(Some(selected));
+    f.render_stateful_widget(table, area, &mut state);
+}
+
+// Per project, the current window next to the previous one and the change,
+// green when it went up and red when it went down
+fn render_comparison(
+    f: &mut ratatui::Frame,
+    area: ratatui::layout::Rect,
+    comparison: &Comparison,
+    colors: &ProjectColors,
+) {
+    use ratatui::{
+        layout::Constraint,
+        style::{Color, Modifier, Style},
+        widgets::{Block, Borders, Cell, Row, Table},
+    };
+
+    let change = |current: u64, previous: u64| match compare::delta(current, previous) {
+        Some(percent) if percent >= 0.5 => {
+            Cell::from(format!("▲ {:.0}%", percent)).style(Style::default().fg(Color::Green))
+        }
+        Some(percent) if percent <= -0.5 => {
+            Cell::from(format!("▼ {:.0}%", -percent)).style(Style::default().fg(Color::Red))
+        }
+        Some(_) => Cell::from("·"),
+        None if current > 0 => Cell::from("new").style(Style::default().fg(Color::Green)),
+        None => Cell::from(""),
+    };
+
+    let rows: Vec<Row> = comparison
+        .rows
+        .iter()
+        .map(|(project, current, previous)| {
+            let mut name = Cell::from(project.clone());
+            if let Some(color) = colors.get(project) {
+                name = name.style(Style::default().fg(color));
+            }
+            Row::new(vec![
+                name,
+                Cell::from(format_duration(*current)),
+                Cell::from(format_duration(*previous)),
+                change(*current, *previous),
+            ])
+        })
+        .collect();
+
+    let (current, previous) = comparison.totals();
+    let footer = Row::new(vec![
+        Cell::from("Total"),
+        Cell::from(format_duration(current)),
+        Cell::from(format_duration(previous)),
+        change(current, previous),
+    ])
+    .style(Style::default().add_modifier(Modifier::BOLD));
+
+    let (now, before) = comparison.window.labels();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(10),
+            Constraint::Length(11),
+            Constraint::Length(11),
+            Constraint::Length(7),
+        ],
+    )
+    .header(
+        Row::new(vec!["Project", now, before, "Change"])
+            .style(Style::default().add_modifier(Modifier::UNDERLINED)),
+    )
+    .footer(footer)
+    .block(
+        Block::default()
+            .title(format!("{} vs {}", now, before.to_lowercase()))
+            .borders(Borders::ALL),
+    );
+
+    f.render_widget(table, area);
+}
+
+// A bar per length bucket, counting the sessions that fell in it
+fn render_session_lengths(f: &mut ratatui::Frame, area: ratatui::layout::Rect, entries: &[Entry]) {
+    use ratatui::{
+        style::{Color, Style},
+        widgets::{Bar, BarChart, BarGroup, Block, Borders},
+    };
+
+    let buckets = length_buckets(entries);
+    let bars: Vec<Bar> = buckets
+        .iter()
+        .map(|&(label, count)| {
+            Bar::default()
+                .label(label.into())
+                .value(count)
+                .text_value(count.to_string())
+        })
+        .collect();
+
+    // Spread the bars over the width, leaving a column between them
+    let width = (area.width.saturating_sub(2) / buckets.len() as u16)
+        .saturating_sub(1)
+        .max(1);
+    let chart = BarChart::default()
+        .block(
+            Block::default()
+                .title("Session lengths")
+                .borders(Borders::ALL),
+        )
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(width)
+        .bar_gap(1)
+        .bar_style(Style::default().fg(Color::Cyan))
+        .value_style(Style::default().fg(Color::Black).bg(Color::Cyan));
+
+    f.render_widget(chart, area);
+}
+
+fn render_calendar(
+    f: &mut ratatui::Frame,
+    area: ratatui::layout::Rect,
+    cursor: NaiveDate,
+    totals: &std::collections::HashMap<NaiveDate, u64>,
+) {
+    use ratatui::{
+        style::{Color, Modifier, Style},
+        text::{Line, Span},
+        widgets::{Block, Borders, Paragraph},
+    };
+
+    let first = cursor.with_day(1).unwrap_or(cursor);
+    let days_in_month = (first + Months::new(1))
+        .signed_duration_since(first)
+        .num_days() as u32;
+
+    let mut lines = vec![
+        Line::raw(""),
+        Line::styled(
+            " Mo   Tu   We   Th   Fr   Sa   Su",
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+    ];
+
+    // Pad the first week up to the weekday the month starts on
+    let mut week: Vec<Span> = (0..first.weekday().num_days_from_monday())
+        .map(|_| Span::raw("     "))
+        .collect();
+
+    for day in 1..=days_in_month {
+        let date = first.with_day(day).unwrap_or(first);
+        let minutes = totals.get(&date).copied().unwrap_or(0);
+
+        // Heat-map shading by tracked hours, untracked days stay plain. The
+        // block glyph carries the intensity on terminals without true color.
+        let (mut style, glyph) = match minutes {
+            0 => (Style::default(), ' '),
+            1..=59 => (Style::default().bg(Color::Rgb(14, 68, 41)), '░'),
+            60..=179 => (Style::default().bg(Color::Rgb(0, 109, 50)), '▒'),
+            180..=359 => (Style::default().bg(Color::Rgb(38, 166, 65)), '▓'),
+            _ => (
+                Style::default()
+                    .bg(Color::Rgb(57, 211, 83))
+                    .fg(Color::Black),
+                '█',
+            ),
+        };
+        if date == cursor {
+            style = style.add_modifier(Modifier::REVERSED | Modifier::BOLD);
+        }
+
+        week.push(Span::styled(format!(" {:>2}{} ", day, glyph), style));
+
+        if week.len() == 7 {
+            lines.push(Line::from(std::mem::take(&mut week)));
+        }
+    }
+    if !week.is_empty() {
+        lines.push(Line::from(week));
+    }
+
+    let selected = totals.get(&cursor).copied().unwrap_or(0);
+    lines.push(Line::raw(""));
+    lines.push(Line::raw(format!(
+        " {}: {}",
+        cursor.format("%a %d %b"),
+        format_duration(selected)
+    )));
+
+    let block = Block::default()
+        .title(format!("Calendar — {}", first.format("%B %Y")))
+        .borders(Borders::ALL);
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+// A ring that fills clockwise from the top as the pomodoro elapses, with the
+// minutes left in the middle; green, then yellow, then red near the end
+fn render_pomodoro(
+    f: &mut ratatui::Frame,
+    area: ratatui::layout::Rect,
+    pomodoro: &PomodoroState,
+    ascii: bool,
+) {
+    use ratatui::{
+        style::{Color, Style},
+        symbols::Marker,
+        text::Line,
+        widgets::{
+            canvas::{Canvas, Points},
+            Block, Borders,
+        },
+    };
+    use std::f64::consts::TAU;
+
+    const STEPS: usize = 96;
+
+    let progress = pomodoro.progress();
+    let color = match progress {
+        p if p < 0.6 => Color::Green,
+        p if p < 0.85 => Color::Yellow,
+        _ => Color::Red,
+    };
+
+    // Two radii make the ring thick enough to read at a glance
+    let ring = |done: bool| -> Vec<(f64, f64)> {
+        (0..STEPS)
+            .filter(|&i| ((i as f64) < progress * STEPS as f64) == done)
+            .flat_map(|i| {
+                let angle = TAU / 4.0 - TAU * i as f64 / STEPS as f64;
+                [0.85, 1.0].map(|radius| (radius * angle.cos(), radius * angle.sin()))
+            })
+            .collect()
+    };
+    let (done, left) = (ring(true), ring(false));
+
+    let remaining = pomodoro.remaining().as_secs();
+    let label = format!("{}:{:02}", remaining / 60, remaining % 60);
+    // One cell in canvas units, to center the label
+    let cell = 2.4 / f64::from(area.width.saturating_sub(2).max(1));
+    let label_x = -(label.len() as f64) * cell / 2.0;
+
+    let canvas = Canvas::default()
+        .block(Block::default().title("Pomodoro").borders(Borders::ALL))
+        .marker(if ascii { Marker::Dot } else { Marker::Braille })
+        .x_bounds([-1.2, 1.2])
+        .y_bounds([-1.2, 1.2])
+        .paint(move |ctx| {
+            ctx.draw(&Points {
+                coords: &left,
+                color: Color::DarkGray,
+            });
+            ctx.draw(&Points {
+                coords: &done,
+                color,
+            });
+            ctx.print(
+                label_x,
+                0.0,
+                Line::styled(label.clone(), Style::default().fg(color)),
+            );
+        });
+
+    f.render_widget(canvas, area);
+}
+
+fn render_goal_gauge(f: &mut ratatui::Frame, area: ratatui::layout::Rect, minutes: u64, goal: f64) {
+    use ratatui::{
+        style::{Color, Style},
+        widgets::Gauge,
+    };
+
+    let goal_minutes = (goal * 60.0).max(1.0);
+    let ratio = (minutes as f64 / goal_minutes).clamp(0.0, 1.0);
+
+    // Fade from red to green as the goal gets closer
+    let color = Color::Rgb((255.0 * (1.0 - ratio)) as u8, (255.0 * ratio) as u8, 0);
+
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(color))
+        .ratio(ratio)
+        .label(format!(
+            "today {} / {}",
+            format_duration(minutes),
+            format_duration(goal_minutes as u64)
+        ));
+
+    f.render_widget(gauge, area);
+}
+
+fn render_confirm(f: &mut ratatui::Frame, confirmation: &Confirmation) {
+    use ratatui::{
+        layout::{Alignment, Rect},
+        style::{Color, Modifier, Style},
+        text::Line,
+        widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    };
+
+    let size = f.area();
+    let width = 60.min(size.width);
+    let height = 6.min(size.height);
+    let area = Rect::new(
+        size.x + (size.width - width) / 2,
+        size.y + (size.height - height) / 2,
+        width,
+        height,
+    );
+
+    let lines = vec![
+        Line::raw(confirmation.question()),
+        Line::raw(""),
+        Line::styled(
+            "y: yes • any other key: no",
+            Style::default().fg(Color::Gray),
+        ),
+    ];
+    let block = Block::default()
+        .title("Confirm")
+        .borders(Borders::ALL)
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        area,
+    );
+}
+
+// The elapsed time in big digits over the session, centered on an
+// otherwise empty screen
+fn render_focus(f: &mut ratatui::Frame, app: &App) {
+    use ratatui::{
+        layout::{Alignment, Rect},
+        style::{Color, Modifier, Style},
+        text::Line,
+        widgets::Paragraph,
+    };
+
+    let (minutes, label, color) = match app.tracking() {
+        Some(session) => (
+            session.elapsed_minutes,
+            describe_session(&session),
+            app.tracking_state.color(),
+        ),
+        None => (0, "Not tracking".to_string(), Color::DarkGray),
+    };
+
+    let time = format!("{:02}:{:02}", minutes / 60, minutes % 60);
+    let mut lines: Vec<Line> = bigtext::lines(&time)
+        .into_iter()
+        .map(|line| Line::styled(line, Style::default().fg(color)))
+        .collect();
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        label,
+        Style::default().add_modifier(Modifier::BOLD),
+    ));
+
+    let size = f.area();
+    let height = (lines.len() as u16).min(size.height);
+    let area = Rect::new(
+        size.x,
+        size.y + (size.height - height) / 2,
+        size.width,
+        height,
+    );
+    f.render_widget(Paragraph::new(lines).alignment(Alignment::Center), area);
+}
+
+// '● LIVE' pulsing with the tick while tracking, a dim '○ idle' otherwise.
+// Without colors (ascii mode) the pulse is bold against dim.
+fn live_indicator(app: &App) -> ratatui::text::Line<'static> {
+    use ratatui::{
+        style::{Color, Modifier, Style},
+        text::Line,
+    };
+
+    let base = |color: Color| {
+        if app.ascii {
+            Style::default()
+        } else {
+            Style::default().fg(color)
+        }
+    };
+
+    if app.is_tracking() {
+        let pulse = if app.spinner_frame.is_multiple_of(2) {
+            Modifier::BOLD
+        } else {
+            Modifier::DIM
+        };
+        Line::styled(" ● LIVE ", base(Color::Green).add_modifier(pulse))
+    } else {
+        Line::styled(
+            " ○ idle ",
+            base(Color::DarkGray).add_modifier(Modifier::DIM),
+        )
+    }
+}
+
+// The running session's elapsed time in the top-right corner, over whatever
+// screen is up, so it's never out of sight. Nothing while idle.
+fn render_running_total(f: &mut ratatui::Frame, app: &App) {
+    use ratatui::{
+        layout::Rect,
+        style::{Modifier, Style},
+        widgets::{Clear, Paragraph},
+    };
+
+    let Some(session) = app.tracking() else {
+        return;
+    };
+
+    let text = format!(
+        " {} {} {} ",
+        app.tracking_state.glyph(),
+        format_duration(session.elapsed_minutes),
+        session.project
+    );
+    let size = f.area();
+    let width = (text.chars().count() as u16).min(size.width / 2);
+    let area = Rect::new(
+        size.x + size.width - width,
+        size.y,
+        width,
+        1.min(size.height),
+    );
+
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(text).style(
+            Style::default()
+                .fg(app.tracking_state.color())
+                .add_modifier(Modifier::BOLD),
+        ),
+        area,
+    );
+}
+
+fn render_long_session_banner(f: &mut ratatui::Frame, minutes: u64, keys: &KeyMap) {
+    use ratatui::{
+        layout::Rect,
+        style::{Color, Modifier, Style},
+        widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    };
+
+    let size = f.area();
+    let area = Rect::new(size.x, size.y, size.width, size.height.min(4));
+
+    let text = format!(
+        "This session has been running for {} — forgot to stop it?\ne: adjust begin • {}: finish at the right time • any other key: keep it",
+        format_duration(minutes),
+        keys.key(Action::Finish)
+    );
+
+    let block = Block::default()
+        .title("Long session")
+        .borders(Borders::ALL)
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+fn render_idle_banner(f: &mut ratatui::Frame, since: Instant) {
+    use ratatui::{
+        layout::Rect,
+        style::{Color, Modifier, Style},
+        widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    };
+
+    let size = f.area();
+    let area = Rect::new(size.x, size.y, size.width, size.height.min(4));
+
+    let minutes = since.elapsed().as_secs() / 60;
+    let text = format!(
+        "You've been idle for {} minutes — still tracking?\nk: keep • d: discard idle time • f: finish now",
+        minutes
+    );
+
+    let block = Block::default().title("Idle").borders(Borders::ALL).style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+fn get_current_tracking(runner: &dyn ZeitRunner) -> String {
+    // Execute 'zeit tracking' and capture the output
+    match runner.run(&["tracking", "--no-colors"]) {
+        Ok(stdout) if stdout.trim().is_empty() => "No active tracking.".to_string(),
+        Ok(stdout) => stdout,
+        Err(_) => "Error getting tracking status.".to_string(),
+    }
+}
+
+// 'project', unless it only differs in case from a known project and the
+// suggested one is taken instead. Asks while the prompt has the terminal.
+fn resolve_project(project: &str, known: &[String]) -> String {
+    let Some(existing) = known
+        .iter()
+        .find(|name| name.as_str() != project && name.to_lowercase() == project.to_lowercase())
+    else {
+        return project.to_string();
+    };
+
+    let question = requestty::Question::confirm("existing")
+        .message(format!(
+            "Did you mean '{}'? (no starts '{}' as a new project)",
+            existing, project
+        ))
+        .default(true)
+        .build();
 
-            f.render_widget(paragraph, chunks[0]);
+    let use_existing = requestty::prompt_one(question)
+        .ok()
+        .and_then(|answer| answer.as_bool())
+        .unwrap_or(true);
+    if use_existing {
+        existing.clone()
+    } else {
+        project.to_string()
+    }
+}
 
-            let instructions =
-                Paragraph::new("q: quit • s: start • f: finish • l: list • d: stats")
-                    .wrap(Wrap { trim: true });
+// Known projects fuzzily matching 'input', best first, e.g. 'ac' offers
+// 'Acme Corp'. What was typed stays last so new projects can still be entered.
+fn complete_project(input: &str, projects: &[String]) -> requestty::question::Completions<String> {
+    use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
 
-            f.render_widget(instructions, chunks[1]);
-        }
-        Screen::List => {
-            let block = Block::default()
-                .title("Tracked Activities")
-                .borders(Borders::ALL);
+    let matcher = SkimMatcherV2::default().ignore_case();
+    let mut matches: Vec<(i64, &String)> = projects
+        .iter()
+        .filter_map(|project| {
+            matcher
+                .fuzzy_match(project, input.trim())
+                .map(|score| (score, project))
+        })
+        .collect();
+    // Stable, so equal scores keep the most recent project first
+    matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
 
-            let paragraph = Paragraph::new(app.list_output.clone())
-                .block(block)
-                .wrap(Wrap { trim: true });
+    let mut completions: requestty::question::Completions<String> = matches
+        .into_iter()
+        .map(|(_, project)| project.clone())
+        .collect();
+    if !input.trim().is_empty() && !completions.iter().any(|project| project == input.trim()) {
+        completions.push(input.to_string());
+    }
+    if completions.is_empty() {
+        completions.push(input.to_string());
+    }
+    completions
+}
 
-            f.render_widget(paragraph, size);
+// Optional time prompts accept an empty answer or anything 'parse_time_input' understands
+fn validate_time(input: &str, formats: &[String]) -> Result<(), String> {
+    if input.trim().is_empty() {
+        return Ok(());
+    }
+    parse_time_input(input, formats).map(|_| ())
+}
 
-            let instructions = Paragraph::new("b: back").wrap(Wrap { trim: true });
+// Read a validated time answer
+fn time_answer(answers: &requestty::Answers, key: &str, formats: &[String]) -> Option<TimeInput> {
+    answers
+        .get(key)
+        .and_then(|a| a.as_string())
+        .filter(|time| !time.trim().is_empty())
+        .and_then(|time| parse_time_input(time, formats).ok())
+}
 
-            f.render_widget(instructions, size);
-        }
-        Screen::Stats => {
-            let block = Block::default().title("Statistics").borders(Borders::ALL);
+// Apply round_to_minutes to a begin/finish (now when left empty) and convert
+// it into a 'zeit' argument. Rounding is confirmed first so it never surprises.
+// Must run while the prompts have the terminal.
+fn rounded_time_arg(time: Option<TimeInput>, label: &str, config: &Config) -> Option<String> {
+    let Some(minutes) = config.round_to_minutes.filter(|&minutes| minutes > 0) else {
+        return time.map(TimeInput::to_zeit_arg);
+    };
 
-            let paragraph = Paragraph::new(app.stats_output.clone())
-                .block(block)
-                .wrap(Wrap { trim: true });
+    let now = Local::now();
+    let exact = time.map_or(now, |time| time.resolve(now));
+    let rounded = round_to_increment(exact, minutes);
+    if rounded.format("%H:%M").to_string() == exact.format("%H:%M").to_string() {
+        return time.map(TimeInput::to_zeit_arg);
+    }
 
-            f.render_widget(paragraph, size);
+    let confirm = requestty::Question::confirm("round")
+        .message(format!(
+            "{} at {} (rounded to {} minutes, no keeps {})?",
+            label,
+            rounded.format("%H:%M"),
+            minutes,
+            exact.format("%H:%M")
+        ))
+        .default(true)
+        .build();
 
-            let instructions = Paragraph::new("b: back").wrap(Wrap { trim: true });
+    let accepted = requestty::prompt_one(confirm)
+        .ok()
+        .and_then(|answer| answer.as_bool())
+        .unwrap_or(false);
 
-            f.render_widget(instructions, size);
-        }
+    if accepted {
+        Some(TimeInput::Absolute(rounded).to_zeit_arg())
+    } else {
+        time.map(TimeInput::to_zeit_arg)
     }
 }
 
-fn get_current_tracking() -> String {
-    // Execute 'zeit tracking' and capture the output
-    let output = Command::new("zeit")
-        .arg("tracking")
-        .arg("--no-colors") // Added '--no-colors' flag
-        .output()
-        .expect("Failed to execute 'zeit tracking'");
-
-    if output.status.success() {
-        let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
-        if stdout.trim().is_empty() {
-            "No active tracking.".to_string()
-        } else {
-            stdout
-        }
-    } else {
-        "Error getting tracking status.".to_string()
+fn start_tracking(
+    runner: &dyn ZeitRunner,
+    config: &Config,
+    tracking: bool,
+    // Attached to the running session if it gets finished first
+    notes: &str,
+    dry_run: bool,
+) -> Result<(), ZeoxError> {
+    if tracking && config.on_start_while_tracking == StartGuard::Abort {
+        return Err("Already tracking, finish the current session first"
+            .to_string()
+            .into());
     }
-}
 
-fn start_tracking() {
-    // Temporarily disable raw mode and leave alternate screen
-    disable_raw_mode().unwrap();
-    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture).unwrap();
+    // Offered when completing the project name
+    let projects = runner
+        .run(&["list", "--no-colors"])
+        .map(|output| recent_projects(&parse_list(&output)))
+        .unwrap_or_default();
+    let known = projects.clone();
+
+    // Temporarily give the terminal back
+    term::suspend();
+
+    // Decide what happens to the running session before asking for the new one
+    let finish_first = match (tracking, config.on_start_while_tracking) {
+        (false, _) | (true, StartGuard::Abort) => false,
+        (true, StartGuard::AutoFinish) => true,
+        (true, StartGuard::Prompt) => {
+            let running_question = requestty::Question::select("running")
+                .message("A session is already running:")
+                .choices(vec!["Finish it first", "Let zeit handle it", "Cancel"])
+                .build();
+
+            // Esc or Ctrl-C count as Cancel
+            let answer = requestty::prompt_one(running_question).ok();
+            match answer.and_then(|answer| answer.as_list_item().map(|item| item.index)) {
+                Some(0) => true,
+                Some(1) => false,
+                _ => {
+                    term::resume();
+                    return Err("Start cancelled".to_string().into());
+                }
+            }
+        }
+    };
 
     // Prompt for project name and task name using requestty
     let project_question = requestty::Question::input("project")
-        .message("Enter project name:")
+        .message("Enter project name (tab completes):")
+        .auto_complete(move |input, _| complete_project(&input, &projects))
         .validate(|input, _| {
             if input.trim().is_empty() {
                 Err("Project name cannot be empty".to_string()) // Appended .to_string()
@@ -242,20 +5125,48 @@ fn start_tracking() {
         .message("Enter task name (optional):")
         .build();
 
-    let begin_question = requestty::Question::input("begin")
-        .message("Enter start time (e.g., '16:00' or '-0:15', leave empty for now):")
+    // Shows the resolved time while typing
+    let begin_question = requestty::Question::custom(
+        "begin",
+        BeginPrompt {
+            formats: config.time_formats.clone(),
+        },
+    )
+    .message("Enter start time (e.g., '16:00', '-0:15' or '-1.5h', leave empty for now):")
+    .build();
+
+    let notes_question = requestty::Question::input("notes")
+        .message("Notes (optional):")
         .build();
 
-    let answers = requestty::prompt(vec![project_question, task_question, begin_question]).unwrap();
+    let answers = requestty::prompt(vec![
+        project_question,
+        task_question,
+        begin_question,
+        notes_question,
+    ]);
+    // Esc or Ctrl-C on any of them
+    let Ok(answers) = answers else {
+        term::resume();
+        return Err("Start cancelled".to_string().into());
+    };
+    let project = answers
+        .get("project")
+        .and_then(|a| a.as_string())
+        .map(|project| resolve_project(&config.project_case.apply(project), &known));
+    let begin_time = rounded_time_arg(
+        time_answer(&answers, "begin", &config.time_formats),
+        "Start",
+        config,
+    );
 
     // Restore terminal settings
-    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture).unwrap();
-    enable_raw_mode().unwrap();
+    term::resume();
 
     // Build the command arguments
     let mut args = vec!["track"];
 
-    if let Some(project_name) = answers.get("project").and_then(|a| a.as_string()) {
+    if let Some(project_name) = &project {
         args.push("--project");
         args.push(project_name);
     }
@@ -267,31 +5178,48 @@ fn start_tracking() {
         }
     }
 
-    if let Some(begin_time) = answers.get("begin").and_then(|a| a.as_string()) {
-        if !begin_time.trim().is_empty() {
-            args.push("--begin");
-            args.push(begin_time);
+    if let Some(begin_time) = &begin_time {
+        args.push("--begin");
+        args.push(begin_time);
+    }
+
+    if let Some(notes) = answers.get("notes").and_then(|a| a.as_string()) {
+        if !notes.trim().is_empty() {
+            args.push("--notes");
+            args.push(notes);
         }
     }
 
     args.push("--no-colors"); // Added '--no-colors' flag
 
-    // Start tracking the specified project and task
-    let output = Command::new("zeit")
-        .args(&args)
-        .output()
-        .expect("Failed to execute 'zeit track'");
+    if !finish_first {
+        return run_zeit(runner, &args, dry_run).map(|_| ());
+    }
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        println!("Failed to start tracking: {}", stderr);
+    match quick_finish(runner, None, notes, dry_run) {
+        // A dry run previews the start along with the finish
+        Err(ZeoxError::DryRun { argv }) => {
+            then_dry_run(argv, run_zeit(runner, &args, dry_run).map(|_| ()))
+        }
+        Err(err) => Err(err),
+        // Don't leave it unsaid that nothing is tracked anymore
+        Ok(()) => run_zeit(runner, &args, dry_run).map(|_| ()).map_err(|err| {
+            ZeoxError::from(format!(
+                "Finished the running session, but nothing was started: {}",
+                err
+            ))
+        }),
     }
 }
 
-fn finish_tracking() {
-    // Temporarily disable raw mode and leave alternate screen
-    disable_raw_mode().unwrap();
-    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture).unwrap();
+fn finish_tracking(
+    runner: &dyn ZeitRunner,
+    config: &Config,
+    notes: &str,
+    dry_run: bool,
+) -> Result<(), ZeoxError> {
+    // Temporarily give the terminal back
+    term::suspend();
 
     // Prompt for optional task and time adjustments using requestty
     let task_question = requestty::Question::input("task")
@@ -300,17 +5228,39 @@ fn finish_tracking() {
 
     let begin_question = requestty::Question::input("begin")
         .message("Adjust start time (optional):")
+        .validate(|input, _| validate_time(input, &config.time_formats))
         .build();
 
     let finish_question = requestty::Question::input("finish")
         .message("Adjust finish time (optional):")
+        .validate(|input, _| validate_time(input, &config.time_formats))
+        .build();
+
+    let notes_question = requestty::Question::input("notes")
+        .message("Notes (optional):")
         .build();
 
-    let answers = requestty::prompt(vec![task_question, begin_question, finish_question]).unwrap();
+    let answers = requestty::prompt(vec![
+        task_question,
+        begin_question,
+        finish_question,
+        notes_question,
+    ]);
+    let Ok(answers) = answers else {
+        term::resume();
+        return Err("Finish cancelled".to_string().into());
+    };
+    // An unchanged begin was already rounded when the session started
+    let begin_time = time_answer(&answers, "begin", &config.time_formats)
+        .and_then(|begin| rounded_time_arg(Some(begin), "Begin", config));
+    let finish_time = rounded_time_arg(
+        time_answer(&answers, "finish", &config.time_formats),
+        "Finish",
+        config,
+    );
 
     // Restore terminal settings
-    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture).unwrap();
-    enable_raw_mode().unwrap();
+    term::resume();
 
     // Build the command arguments
     let mut args = vec!["finish"];
@@ -322,62 +5272,831 @@ fn finish_tracking() {
         }
     }
 
-    if let Some(begin_time) = answers.get("begin").and_then(|a| a.as_string()) {
-        if !begin_time.trim().is_empty() {
-            args.push("--begin");
-            args.push(begin_time);
-        }
+    if let Some(begin_time) = &begin_time {
+        args.push("--begin");
+        args.push(begin_time);
     }
 
-    if let Some(finish_time) = answers.get("finish").and_then(|a| a.as_string()) {
-        if !finish_time.trim().is_empty() {
-            args.push("--finish");
-            args.push(finish_time);
-        }
+    if let Some(finish_time) = &finish_time {
+        args.push("--finish");
+        args.push(finish_time);
+    }
+
+    // Typed notes go after the ones jotted down during the session
+    let typed = answers
+        .get("notes")
+        .and_then(|a| a.as_string())
+        .map(str::trim)
+        .unwrap_or("");
+    let notes = match (notes.is_empty(), typed.is_empty()) {
+        (_, true) => notes.to_string(),
+        (true, false) => typed.to_string(),
+        (false, false) => format!("{}\n{}", notes, typed),
+    };
+    if !notes.is_empty() {
+        args.push("--notes");
+        args.push(&notes);
     }
 
     args.push("--no-colors"); // Added '--no-colors' flag
 
     // Finish the current tracking session
-    let output = Command::new("zeit")
-        .args(&args)
-        .output()
-        .expect("Failed to execute 'zeit finish'");
+    run_zeit(runner, &args, dry_run).map(|_| ())
+}
+
+// What to continue with after switching tasks
+struct Switch {
+    project: String,
+    task: String,
+    // Where the old session ends and the new one begins, None for now
+    at: Option<String>,
+}
+
+// Ask for the task of a quick project, None when cancelled
+fn prompt_quick_task(project: &str) -> Option<String> {
+    let task_question = requestty::Question::input("task")
+        .message(format!("Task on {} (optional):", project))
+        .build();
+
+    let answer = term::suspended(|| requestty::prompt_one(task_question));
+
+    answer
+        .ok()
+        .and_then(|answer| answer.as_string().map(|task| task.trim().to_string()))
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        println!("Failed to finish tracking: {}", stderr);
+// Ask for the next task on 'project', one terminal round-trip for the whole switch
+fn prompt_switch(config: &Config, project: &str) -> Result<Switch, ZeoxError> {
+    // Temporarily give the terminal back
+    term::suspend();
+
+    let task_question = requestty::Question::input("task")
+        .message(format!("Next task on {}:", project))
+        .build();
+
+    let task = requestty::prompt_one(task_question)
+        .ok()
+        .and_then(|answer| answer.as_string().map(|task| task.trim().to_string()));
+    // Both sides of the switch share one (possibly rounded) moment, leaving no gap
+    let at = task
+        .as_ref()
+        .and_then(|_| rounded_time_arg(None, "Switch", config));
+
+    // Restore terminal settings
+    term::resume();
+
+    let task = task.ok_or_else(|| ZeoxError::from("Switch cancelled".to_string()))?;
+    Ok(Switch {
+        project: project.to_string(),
+        task,
+        at,
+    })
+}
+
+// Finish with both ends pinned, as set on the finish form
+fn finish_between(
+    runner: &dyn ZeitRunner,
+    begin: DateTime<Local>,
+    finish: DateTime<Local>,
+    notes: &str,
+    dry_run: bool,
+) -> Result<(), ZeoxError> {
+    let begin = begin.to_rfc3339();
+    let finish = finish.to_rfc3339();
+    let mut args = vec!["finish", "--begin", &begin, "--finish", &finish];
+    if !notes.is_empty() {
+        args.extend(["--notes", notes]);
+    }
+    args.push("--no-colors");
+
+    run_zeit(runner, &args, dry_run).map(|_| ())
+}
+
+fn quick_finish_at(
+    runner: &dyn ZeitRunner,
+    finish: Option<&str>,
+    notes: &str,
+    dry_run: bool,
+) -> Result<(), ZeoxError> {
+    let mut args = vec!["finish"];
+    if let Some(finish) = finish {
+        args.extend(["--finish", finish]);
+    }
+    if !notes.is_empty() {
+        args.extend(["--notes", notes]);
+    }
+    args.push("--no-colors");
+
+    run_zeit(runner, &args, dry_run).map(|_| ())
+}
+
+fn start_switched(
+    runner: &dyn ZeitRunner,
+    switch: &Switch,
+    dry_run: bool,
+) -> Result<(), ZeoxError> {
+    let mut args = vec!["track", "--project", &switch.project];
+    if !switch.task.is_empty() {
+        args.extend(["--task", &switch.task]);
+    }
+    if let Some(begin) = &switch.at {
+        args.extend(["--begin", begin]);
+    }
+    args.push("--no-colors");
+
+    run_zeit(runner, &args, dry_run).map(|_| ())
+}
+
+// Ask for the running session's task (kept as is by default) and a note,
+// None when cancelled
+fn prompt_note(task: &str) -> Option<(String, Option<String>)> {
+    let questions = vec![
+        requestty::Question::input("task")
+            .message("Task:")
+            .default(task)
+            .build(),
+        requestty::Question::input("note")
+            .message("Note for the running session (optional):")
+            .build(),
+    ];
+
+    let answers = term::suspended(|| requestty::prompt(questions)).ok()?;
+    let text = |name: &str| {
+        answers
+            .get(name)
+            .and_then(|answer| answer.as_string())
+            .map(|text| text.trim().to_string())
+            .unwrap_or_default()
+    };
+
+    let note = Some(text("note")).filter(|note| !note.is_empty());
+    Some((text("task"), note))
+}
+
+// Ask for the arguments of a raw zeit command, None when left empty
+fn prompt_zeit_command(last: &str) -> Option<String> {
+    let command_question = requestty::Question::input("command")
+        .message("zeit")
+        .default(last)
+        .build();
+
+    let answer = term::suspended(|| requestty::prompt_one(command_question));
+
+    answer
+        .ok()
+        .and_then(|answer| answer.as_string().map(str::to_string))
+        .filter(|line| !line.trim().is_empty())
+}
+
+// Erase 'ids' one at a time, after a typed confirmation when 'confirm' is set,
+// printing progress while the prompt has the terminal. Returns what went wrong
+// for each failed entry.
+fn batch_delete(
+    runner: &dyn ZeitRunner,
+    ids: &[String],
+    confirm: bool,
+) -> Result<Vec<String>, ZeoxError> {
+    // Temporarily give the terminal back
+    term::suspend();
+
+    let expected = format!("delete {}", ids.len());
+    let confirm_question = requestty::Question::input("confirm")
+        .message(format!(
+            "This erases {} entries for good. Type '{}' to confirm:",
+            ids.len(),
+            expected
+        ))
+        .build();
+
+    let confirmed = !confirm
+        || requestty::prompt_one(confirm_question)
+            .ok()
+            .and_then(|answer| answer.as_string().map(|text| text.trim() == expected))
+            .unwrap_or(false);
+
+    let mut failures = Vec::new();
+    if confirmed {
+        let mut stdout = io::stdout();
+        for (i, id) in ids.iter().enumerate() {
+            let _ = write!(stdout, "\rDeleting {}/{}…", i + 1, ids.len());
+            let _ = stdout.flush();
+
+            if let Err(err) = runner.run(&["erase", id, "--no-colors"]) {
+                failures.push(format!("{}: {}", id, err));
+            }
+        }
+        let _ = writeln!(stdout);
+    }
+
+    // Restore terminal settings
+    term::resume();
+
+    if confirmed {
+        Ok(failures)
+    } else {
+        Err("Delete cancelled".to_string().into())
+    }
+}
+
+// The running entry, 'zeit tracking' doesn't print its id
+fn running_entry(runner: &dyn ZeitRunner) -> Result<Entry, ZeoxError> {
+    parse_list(&runner.run(&["list", "--no-colors"])?)
+        .into_iter()
+        .find(|entry| entry.running)
+        .ok_or_else(|| ZeoxError::from("Couldn't find the running entry".to_string()))
+}
+
+fn running_entry_id(runner: &dyn ZeitRunner) -> Result<String, ZeoxError> {
+    running_entry(runner).map(|entry| entry.id)
+}
+
+// The entry that finished last, i.e. the one a finish just closed
+fn last_finished_entry(runner: &dyn ZeitRunner) -> Option<Entry> {
+    parse_list(&runner.run(&["list", "--no-colors"]).ok()?)
+        .into_iter()
+        .filter(|entry| !entry.running)
+        .max_by_key(|entry| entry.finish)
+}
+
+// Drop a session that was started by mistake, if it's still the running one
+fn undo_start(runner: &dyn ZeitRunner, id: &str, dry_run: bool) -> Result<(), ZeoxError> {
+    if !dry_run && running_entry_id(runner).ok().as_deref() == Some(id) {
+        quick_finish(runner, None, "", false)?;
+    }
+    run_zeit(runner, &["erase", id, "--no-colors"], dry_run).map(|_| ())
+}
+
+// Re-open a finished session: track it again from its original begin, then
+// erase the closed entry
+fn undo_finish(runner: &dyn ZeitRunner, entry: &Entry, dry_run: bool) -> Result<(), ZeoxError> {
+    let begin = entry.begin.to_rfc3339();
+    let mut args = vec!["track", "--project", &entry.project];
+    if !entry.task.is_empty() {
+        args.push("--task");
+        args.push(&entry.task);
     }
+    args.extend(["--begin", &begin, "--no-colors"]);
+
+    run_zeit(runner, &args, dry_run)?;
+    run_zeit(runner, &["erase", &entry.id, "--no-colors"], dry_run).map(|_| ())
+}
+
+fn adjust_begin(runner: &dyn ZeitRunner, config: &Config, dry_run: bool) -> Result<(), ZeoxError> {
+    let id = running_entry_id(runner)?;
+
+    let begin_question = requestty::Question::input("begin")
+        .message("When did this session actually start? (e.g., '9:30' or '-0:20'):")
+        .validate(|input, _| {
+            let begin = parse_time_input(input, &config.time_formats)?;
+            if begin.resolve(Local::now()) > Local::now() {
+                Err("The start time can't be in the future".to_string())
+            } else {
+                Ok(())
+            }
+        })
+        .build();
+
+    let answer = term::suspended(|| requestty::prompt_one(begin_question));
+
+    let begin = answer
+        .ok()
+        .as_ref()
+        .and_then(|a| a.as_string())
+        .and_then(|input| parse_time_input(input, &config.time_formats).ok())
+        .ok_or_else(|| ZeoxError::from("Begin time unchanged".to_string()))?;
+
+    let begin_arg = begin.to_zeit_arg();
+    run_zeit(
+        runner,
+        &["entry", "--begin", &begin_arg, &id, "--no-colors"],
+        dry_run,
+    )
+    .map(|_| ())
 }
 
-fn get_list_output() -> String {
-    // Execute 'zeit list' and capture the output
-    let output = Command::new("zeit")
-        .arg("list")
-        .arg("--no-colors") // Added '--no-colors' flag
-        .output()
-        .expect("Failed to execute 'zeit list'");
+// Where to split 'entry' for what was typed: '+0:30' counts from its begin,
+// '-0:30' back from its end, and a time of day lands on the entry's own day
+fn split_point(input: &str, entry: &Entry, formats: &[String]) -> Result<DateTime<Local>, String> {
+    let begin = entry.begin.with_timezone(&Local);
+    let end = if entry.running {
+        Local::now()
+    } else {
+        entry.finish.with_timezone(&Local)
+    };
+    let inside = |time: DateTime<Local>| time > begin && time < end;
+
+    let time = match parse_time_input(input, formats)? {
+        TimeInput::Relative(offset) if offset < chrono::Duration::zero() => end + offset,
+        TimeInput::Relative(offset) => begin + offset,
+        // Times of day come back as today, try the days the entry spans
+        TimeInput::Absolute(time) => [begin.date_naive(), end.date_naive()]
+            .iter()
+            .filter_map(|day| {
+                day.and_time(time.time())
+                    .and_local_timezone(Local)
+                    .earliest()
+            })
+            .find(|&time| inside(time))
+            .unwrap_or(time),
+    };
 
-    if output.status.success() {
-        String::from_utf8_lossy(&output.stdout).into_owned()
+    if inside(time) {
+        Ok(time)
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        format!("Error getting list: {}", stderr)
+        Err(format!(
+            "The split has to lie between {} and {}",
+            begin.format("%H:%M"),
+            end.format("%H:%M")
+        ))
     }
 }
 
-fn get_stats_output() -> String {
-    // Execute 'zeit stats' and capture the output
-    let output = Command::new("zeit")
-        .arg("stats")
-        .arg("--no-colors") // Added '--no-colors' flag
-        .output()
-        .expect("Failed to execute 'zeit stats'");
+// Cut 'entry' in two at a prompted time: the original ends there and a new
+// entry with the same project and task covers the rest. A running entry
+// keeps running as the new one.
+fn split_entry(
+    runner: &dyn ZeitRunner,
+    config: &Config,
+    entry: &Entry,
+    dry_run: bool,
+) -> Result<DateTime<Local>, ZeoxError> {
+    let split_question = requestty::Question::input("split")
+        .message(format!(
+            "Split '{}' at (e.g., '14:30', '+1:00' from its begin or '-0:20' from its end):",
+            entry.task
+        ))
+        .validate(|input, _| split_point(input, entry, &config.time_formats).map(|_| ()))
+        .build();
+
+    let answer = term::suspended(|| requestty::prompt_one(split_question));
 
-    if output.status.success() {
-        String::from_utf8_lossy(&output.stdout).into_owned()
+    let at = answer
+        .ok()
+        .as_ref()
+        .and_then(|a| a.as_string())
+        .and_then(|input| split_point(input, entry, &config.time_formats).ok())
+        .ok_or_else(|| ZeoxError::from("Split cancelled".to_string()))?;
+
+    let split = at.to_rfc3339();
+    let finish = entry.finish.to_rfc3339();
+    let mut commands = vec![if entry.running {
+        vec!["finish", "--finish", &split, "--no-colors"]
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        format!("Error getting stats: {}", stderr)
+        vec!["entry", "--finish", &split, &entry.id, "--no-colors"]
+    }];
+
+    let mut track = vec!["track", "--project", &entry.project];
+    if !entry.task.is_empty() {
+        track.extend(["--task", &entry.task]);
+    }
+    track.extend(["--begin", &split, "--no-colors"]);
+    commands.push(track);
+
+    if !entry.running {
+        commands.push(vec!["finish", "--finish", &finish, "--no-colors"]);
+    }
+
+    run_zeit_all(runner, &commands, dry_run)?;
+    Ok(at)
+}
+
+// A day typed as YYYY-MM-DD, 'today' or 'yesterday'
+fn parse_day(input: &str) -> Result<NaiveDate, String> {
+    let today = Local::now().date_naive();
+    match input.trim() {
+        "today" => Ok(today),
+        "yesterday" => Ok(today.pred_opt().unwrap_or(today)),
+        input => NaiveDate::parse_from_str(input, "%Y-%m-%d")
+            .map_err(|_| "Expected YYYY-MM-DD, 'today' or 'yesterday'".to_string()),
+    }
+}
+
+fn prompt_day() -> Option<NaiveDate> {
+    let question = requestty::Question::input("day")
+        .message("Go to date:")
+        .validate(|input, _| parse_day(input).map(|_| ()))
+        .build();
+    let answer = term::suspended(|| requestty::prompt_one(question));
+
+    answer
+        .ok()
+        .as_ref()
+        .and_then(|a| a.as_string())
+        .and_then(|input| parse_day(input).ok())
+}
+
+// Ask for a new value of 'setting', None when cancelled
+fn prompt_setting(setting: Setting, current: &str) -> Option<String> {
+    let question = requestty::Question::input("value")
+        .message(format!(
+            "{} ({}, empty to unset):",
+            setting.key(),
+            setting.description()
+        ))
+        .default(if current == "unset" { "" } else { current })
+        .validate(|input, _| setting.check(input))
+        .build();
+
+    let answer = term::suspended(|| requestty::prompt_one(question));
+
+    answer
+        .ok()
+        .as_ref()
+        .and_then(|a| a.as_string())
+        .map(String::from)
+}
+
+// Ask for the invoice period, defaulting to the days the entries cover, and
+// where to save it besides the clipboard
+fn prompt_invoice(first: Option<NaiveDate>, last: Option<NaiveDate>) -> Result<Export, ZeoxError> {
+    let date_question = |name: &str, message: &str, default: Option<NaiveDate>| {
+        requestty::Question::input(name)
+            .message(message)
+            .default(default.map(|day| day.to_string()).unwrap_or_default())
+            .validate(|input, _| {
+                NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d")
+                    .map(|_| ())
+                    .map_err(|_| "Expected YYYY-MM-DD".to_string())
+            })
+            .build()
+    };
+
+    let questions = vec![
+        date_question("since", "Invoice from:", first),
+        date_question("until", "Invoice until:", last),
+        requestty::Question::input("output")
+            .message("Save to file (optional, it's copied either way):")
+            .build(),
+    ];
+
+    let answers = term::suspended(|| requestty::prompt(questions));
+
+    let answers = answers.map_err(|_| ZeoxError::from("Invoice cancelled".to_string()))?;
+    let date = |key: &str| {
+        answers
+            .get(key)
+            .and_then(|a| a.as_string())
+            .and_then(|input| NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d").ok())
+    };
+    let output = answers
+        .get("output")
+        .and_then(|a| a.as_string())
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty());
+
+    Ok(Export {
+        format: "invoice".to_string(),
+        since: date("since"),
+        until: date("until"),
+        output,
+    })
+}
+
+fn rename_task(
+    runner: &dyn ZeitRunner,
+    id: &str,
+    task: &str,
+    dry_run: bool,
+) -> Result<(), ZeoxError> {
+    run_zeit(
+        runner,
+        &["entry", "--task", task, id, "--no-colors"],
+        dry_run,
+    )
+    .map(|_| ())
+}
+
+// e.g. 'Begin 3m earlier'
+fn describe_nudge(minutes: i64) -> String {
+    match minutes {
+        0 => "Begin unchanged".to_string(),
+        m if m < 0 => format!("Begin {}m earlier", -m),
+        m => format!("Begin {}m later", m),
+    }
+}
+
+// Shift the running entry's begin by 'minutes', negative is earlier
+fn nudge_running_begin(
+    runner: &dyn ZeitRunner,
+    minutes: i64,
+    dry_run: bool,
+) -> Result<(), ZeoxError> {
+    let entry = running_entry(runner)?;
+    let begin = (entry.begin + chrono::Duration::minutes(minutes)).to_rfc3339();
+    run_zeit(
+        runner,
+        &["entry", "--begin", &begin, &entry.id, "--no-colors"],
+        dry_run,
+    )
+    .map(|_| ())
+}
+
+fn quick_finish(
+    runner: &dyn ZeitRunner,
+    idle: Option<Duration>,
+    notes: &str,
+    dry_run: bool,
+) -> Result<(), ZeoxError> {
+    let mut args = vec!["finish".to_string()];
+
+    // Move the finish time back to when activity stopped
+    if let Some(idle) = idle {
+        let offset = -chrono::Duration::minutes((idle.as_secs() / 60) as i64);
+        args.push("--finish".to_string());
+        args.push(TimeInput::Relative(offset).to_zeit_arg());
+    }
+
+    if !notes.is_empty() {
+        args.push("--notes".to_string());
+        args.push(notes.to_string());
+    }
+
+    args.push("--no-colors".to_string());
+
+    run_zeit(runner, &args, dry_run).map(|_| ())
+}
+
+// Run a zeit command that changes data, keeping the argv around for error reports.
+// In dry-run mode the command is only reported back, never executed.
+fn run_zeit<S: AsRef<str>>(
+    runner: &dyn ZeitRunner,
+    args: &[S],
+    dry_run: bool,
+) -> Result<String, ZeoxError> {
+    let args: Vec<&str> = args.iter().map(|a| a.as_ref()).collect();
+
+    if dry_run {
+        return Err(ZeoxError::DryRun {
+            argv: runner.argv(&args),
+        });
+    }
+
+    runner.run(&args)
+}
+
+// Run zeit commands in order, stopping at the first that fails. In dry-run
+// mode they're all reported back as one preview.
+fn run_zeit_all(
+    runner: &dyn ZeitRunner,
+    commands: &[Vec<&str>],
+    dry_run: bool,
+) -> Result<(), ZeoxError> {
+    if dry_run {
+        let argvs = commands.iter().map(|args| runner.argv(args)).collect();
+        return Err(chained_dry_run(argvs));
+    }
+
+    for args in commands {
+        runner.run(args)?;
+    }
+    Ok(())
+}
+
+// Under dry-run, a step's preview followed by the next one's, so actions
+// made of several zeit commands show all of them
+fn then_dry_run(argv: Vec<String>, next: Result<(), ZeoxError>) -> Result<(), ZeoxError> {
+    match next {
+        Err(ZeoxError::DryRun { argv: next }) => Err(chained_dry_run(vec![argv, next])),
+        Err(err) => Err(err),
+        Ok(()) => Err(ZeoxError::DryRun { argv }),
+    }
+}
+
+// One dry-run preview for several commands, as 'a && b'
+fn chained_dry_run(argvs: Vec<Vec<String>>) -> ZeoxError {
+    ZeoxError::DryRun {
+        argv: argvs.join(&"&&".to_string()),
+    }
+}
+
+// $EDITOR, or the first of vi/nano found on PATH
+fn find_editor() -> Option<String> {
+    if let Some(editor) = env::var("EDITOR").ok().filter(|e| !e.trim().is_empty()) {
+        return Some(editor);
+    }
+
+    let path = env::var_os("PATH")?;
+    ["vi", "nano"]
+        .into_iter()
+        .find(|name| env::split_paths(&path).any(|dir| dir.join(name).is_file()))
+        .map(str::to_string)
+}
+
+// Hand 'path' to $EDITOR, the TUI is suspended until it exits
+fn open_in_editor(path: &std::path::Path) -> Result<(), String> {
+    let editor = find_editor().ok_or("No editor found, set $EDITOR")?;
+
+    // $EDITOR may carry arguments, e.g. 'code --wait'
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().ok_or("No editor found, set $EDITOR")?;
+
+    let status = term::suspended(|| Command::new(program).args(parts).arg(path).status());
+
+    match status {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(format!("{} exited with {}", program, status)),
+        Err(err) => Err(format!("Failed to launch {}: {}", program, err)),
+    }
+}
+
+fn get_today_minutes(runner: &dyn ZeitRunner) -> u64 {
+    get_minutes_since(runner, chrono::Local::now().date_naive())
+}
+
+fn get_year_minutes(runner: &dyn ZeitRunner) -> u64 {
+    let today = chrono::Local::now().date_naive();
+    get_minutes_since(runner, today.with_ordinal(1).unwrap_or(today))
+}
+
+fn get_minutes_since(runner: &dyn ZeitRunner, day: NaiveDate) -> u64 {
+    // Everything since local midnight of 'day', including a running session up to now
+    let midnight = day
+        .and_hms_opt(0, 0, 0)
+        .and_then(|time| time.and_local_timezone(chrono::Local).earliest());
+    let Some(midnight) = midnight else {
+        return 0;
+    };
+
+    let since = midnight.to_rfc3339();
+    let Ok(output) = runner.run(&["list", "--since", &since, "--no-colors"]) else {
+        return 0;
+    };
+
+    parse_list(&output)
+        .iter()
+        .map(|entry| entry.minutes())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use runner::CannedRunner;
+
+    const TRACKING: &str = "▶ tracking review on acme for 1:05h\n";
+    const LIST: &str = "\
+a1 review on acme from 2024-10-16 09:00 +0000 to 2024-10-16 10:05 +0000 (1:05h)
+b2 emails on admin from 2024-10-16 08:30 +0000 to 2024-10-16 09:00 +0000 (0:30h)
+    triage
+";
+
+    fn canned_app() -> (App, Arc<CannedRunner>) {
+        let runner = Arc::new(CannedRunner {
+            tracking: TRACKING.to_string(),
+            list: LIST.to_string(),
+            ..CannedRunner::default()
+        });
+        let app = App::with_runner(Config::default(), KeyMap::default(), runner.clone());
+        (app, runner)
+    }
+
+    #[test]
+    fn canned_output_is_parsed() {
+        let (mut app, _) = canned_app();
+        app.after_mutation();
+        app.list_output.ensure_fresh(app.runner.as_ref());
+
+        let session = app.tracking().unwrap();
+        assert_eq!(
+            (session.project.as_str(), session.task.as_str()),
+            ("acme", "review")
+        );
+        assert_eq!(session.elapsed_minutes, 65);
+
+        let entries = &app.list_output.entries;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].project, "admin");
+        assert_eq!(entries[1].notes, "triage");
+        assert_eq!(entries[1].minutes(), 30);
+        // Parsed, not kept as text as well
+        assert!(app.list_output.text.is_empty());
+    }
+
+    #[test]
+    fn background_list_refresh_keeps_old_entries_until_done() {
+        let (mut app, _) = canned_app();
+        app.list_output.ensure_fresh(app.runner.as_ref());
+        app.list_output.entries.pop();
+
+        let (tx, rx) = mpsc::channel();
+        app.list_output
+            .refresh_in_background(Screen::List, &tx, &app.runner, false);
+        for (_, refresh) in rx.iter() {
+            match refresh {
+                Refresh::Line(line) => {
+                    app.list_output.append(&line);
+                    assert_eq!(app.list_output.entries.len(), 1);
+                }
+                Refresh::Done(error) => {
+                    app.list_output.done(error);
+                    break;
+                }
+            }
+        }
+
+        assert_eq!(app.list_output.entries.len(), 2);
+        assert!(app.list_output.text.is_empty());
+        assert!(!app.list_output.in_flight);
+    }
+
+    #[test]
+    fn dry_run_finish_only_reports_the_command() {
+        let (mut app, runner) = canned_app();
+        app.after_mutation();
+        app.dry_run = true;
+
+        let result = quick_finish(app.runner.as_ref(), None, "", app.dry_run);
+        assert!(matches!(
+            result,
+            Err(ZeoxError::DryRun { ref argv }) if argv == &["zeit", "finish", "--no-colors"]
+        ));
+
+        // The same through auto-finish, which is due right now
+        let now = Local::now();
+        app.auto_finish = Some(now.time());
+        app.auto_finish_checked = now - chrono::Duration::seconds(1);
+        app.check_auto_finish();
+
+        assert_eq!(
+            app.status.as_deref(),
+            Some("dry run: zeit finish --no-colors")
+        );
+        assert!(app.is_tracking());
+        assert!(!runner
+            .calls
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|argv| argv[1] == "finish"));
+    }
+
+    #[test]
+    fn chained_dry_runs_preview_every_command() {
+        let runner = CannedRunner::default();
+        let finish = vec!["zeit".to_string(), "finish".to_string()];
+        let track = run_zeit(&runner, &["track", "--project", "acme"], true).map(|_| ());
+
+        let Err(ZeoxError::DryRun { argv }) = then_dry_run(finish, track) else {
+            panic!("expected a dry run");
+        };
+        assert_eq!(argv.join(" "), "zeit finish && zeit track --project=acme");
+        assert!(runner.calls.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn dry_run_of_several_commands_runs_none() {
+        let runner = CannedRunner::default();
+        let commands = [
+            vec!["entry", "--finish", "12:00", "a1"],
+            vec!["track", "--project", "acme", "--begin", "12:00"],
+            vec!["finish", "--finish", "13:00"],
+        ];
+
+        let Err(ZeoxError::DryRun { argv }) = run_zeit_all(&runner, &commands, true) else {
+            panic!("expected a dry run");
+        };
+        assert_eq!(
+            argv.join(" "),
+            "zeit entry --finish=12:00 a1 && zeit track --project=acme --begin=12:00 \
+             && zeit finish --finish=13:00"
+        );
+        assert!(runner.calls.lock().unwrap().is_empty());
+
+        run_zeit_all(&runner, &commands, false).unwrap();
+        assert_eq!(runner.calls.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn scrolls_are_clamped_to_the_new_bottom() {
+        let (mut app, _) = canned_app();
+        app.stats_output.text = "line\n".repeat(100);
+        app.command_output = "line\n".repeat(50);
+        for i in 0..30 {
+            app.log(format!("message {}", i));
+        }
+
+        // At the bottom of a 14 row terminal, 10 rows of content
+        app.stats_scroll = 90;
+        app.command_scroll = 40;
+        app.log_scroll = 20;
+
+        // Resized to 40 rows, still shorter than the content
+        app.clamp_scrolls(40);
+        assert_eq!(app.stats_scroll, 64);
+        assert_eq!(app.command_scroll, 14);
+        assert_eq!(app.log_scroll, 0);
+
+        // Shrinking further moves the bottom down, so nothing changes
+        app.clamp_scrolls(20);
+        assert_eq!((app.stats_scroll, app.command_scroll), (64, 14));
+
+        // Offsets above the new bottom stay where they are
+        app.stats_scroll = 10;
+        app.clamp_scrolls(40);
+        assert_eq!(app.stats_scroll, 10);
+
+        // Taller than everything, nothing left to scroll
+        app.clamp_scrolls(200);
+        assert_eq!((app.stats_scroll, app.command_scroll), (0, 0));
     }
 }
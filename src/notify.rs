@@ -0,0 +1,22 @@
+use notify_rust::Notification;
+use std::{sync::mpsc::Sender, thread};
+
+// Show a desktop notification without blocking the UI. There may be no
+// notification daemon at all (headless, SSH), so failures only get logged.
+pub fn send(summary: &str, body: &str, log: &Sender<String>) {
+    let summary = summary.to_string();
+    let body = body.to_string();
+    let log = log.clone();
+
+    thread::spawn(move || {
+        let result = Notification::new()
+            .appname("zeox")
+            .summary(&summary)
+            .body(&body)
+            .show();
+
+        if let Err(err) = result {
+            let _ = log.send(format!("Notification failed: {}", err));
+        }
+    });
+}
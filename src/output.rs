@@ -0,0 +1,227 @@
+use std::{env, sync::Mutex};
+
+// Remove ANSI escape sequences. Every zeit call passes --no-colors, but not
+// every zeit version honors it everywhere and escapes would corrupt parsing.
+pub fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        // 8-bit CSI, as emitted by some older terminal libraries
+        if c == '\u{9b}' {
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+            continue;
+        }
+
+        if c != '\x1b' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            // CSI: ESC [ params... final byte in '@'..='~'
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: ESC ] ... terminated by BEL or ESC \
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            // DCS, SOS, PM and APC strings, terminated by ESC \
+            Some('P') | Some('X') | Some('^') | Some('_') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            // Two-character sequences like ESC ( B
+            Some('(') | Some(')') => {
+                chars.next();
+            }
+            _ => {}
+        }
+    }
+
+    out
+}
+
+// Output that had to be decoded lossily. The same output comes back on every
+// refresh, so each problem is only passed on once.
+struct LossyDecodes {
+    pending: Vec<String>,
+    seen: Vec<String>,
+}
+
+static LOSSY_DECODES: Mutex<LossyDecodes> = Mutex::new(LossyDecodes {
+    pending: Vec::new(),
+    seen: Vec::new(),
+});
+
+// Everything newly mangled by decoding since the last call, with the raw bytes
+pub fn take_lossy_warnings() -> Vec<String> {
+    LOSSY_DECODES
+        .lock()
+        .map(|mut decodes| std::mem::take(&mut decodes.pending))
+        .unwrap_or_default()
+}
+
+// Captured zeit output as displayable, parseable text
+pub fn clean_output(bytes: &[u8]) -> String {
+    strip_ansi(&decode(bytes))
+}
+
+fn decode(bytes: &[u8]) -> String {
+    decode_as(bytes, locale_is_latin1())
+}
+
+fn decode_as(bytes: &[u8], latin1: bool) -> String {
+    let err = match std::str::from_utf8(bytes) {
+        Ok(text) => return text.to_string(),
+        Err(err) => err,
+    };
+
+    // A Latin-1 locale maps every byte to a character, so nothing is lost
+    if latin1 {
+        return bytes.iter().map(|&b| b as char).collect();
+    }
+
+    if let Ok(mut decodes) = LOSSY_DECODES.lock() {
+        let warning = describe_invalid(bytes, err.valid_up_to());
+        if !decodes.seen.contains(&warning) {
+            decodes.seen.push(warning.clone());
+            decodes.pending.push(warning);
+        }
+    }
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+// e.g. 'invalid UTF-8 at byte 4 in "Caf…": e9 20 6f 6e'
+fn describe_invalid(bytes: &[u8], at: usize) -> String {
+    let line_start = bytes[..at]
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+    let context = String::from_utf8_lossy(&bytes[line_start..at]);
+    let raw: Vec<String> = bytes[at..]
+        .iter()
+        .take(8)
+        .map(|b| format!("{:02x}", b))
+        .collect();
+
+    format!(
+        "invalid UTF-8 at byte {} in \"{}…\": {}",
+        at,
+        context.trim(),
+        raw.join(" ")
+    )
+}
+
+// The effective charset is the first of LC_ALL, LC_CTYPE, LANG that is set,
+// e.g. 'de_DE.ISO-8859-1'
+fn locale_is_latin1() -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default();
+    is_latin1(&locale)
+}
+
+fn is_latin1(locale: &str) -> bool {
+    let locale = locale.to_lowercase();
+    let charset = locale.split_once('.').map_or("", |(_, charset)| charset);
+    ["iso-8859-1", "iso8859-1", "iso88591", "latin1", "cp1252"]
+        .iter()
+        .any(|name| charset.starts_with(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_csi_colors() {
+        assert_eq!(
+            strip_ansi("\x1b[1;32macme\x1b[0m  \x1b[38;5;208mreview\x1b[m"),
+            "acme  review"
+        );
+    }
+
+    #[test]
+    fn strips_osc_titles() {
+        assert_eq!(strip_ansi("\x1b]0;zeit\x07tracked"), "tracked");
+        assert_eq!(strip_ansi("\x1b]2;zeit\x1b\\tracked"), "tracked");
+        assert_eq!(
+            strip_ansi("\x1b]8;;https://zeit.observer\x1b\\link\x1b]8;;\x1b\\"),
+            "link"
+        );
+    }
+
+    #[test]
+    fn strips_8bit_csi() {
+        assert_eq!(strip_ansi("\u{9b}31macme\u{9b}0m 1h"), "acme 1h");
+    }
+
+    #[test]
+    fn strips_dcs_and_apc_strings() {
+        assert_eq!(strip_ansi("a\x1bPq#0;2;0;0;0\x1b\\b"), "ab");
+        assert_eq!(strip_ansi("a\x1b_Gf=100;AAAA\x1b\\b"), "ab");
+        assert_eq!(strip_ansi("a\x1b(Bb"), "ab");
+    }
+
+    #[test]
+    fn plain_text_passes_through() {
+        let text = "▶ acme on review from 09:00 (1h 05m) [note: café]\n";
+        assert_eq!(strip_ansi(text), text);
+        assert_eq!(strip_ansi(""), "");
+    }
+
+    #[test]
+    fn invalid_utf8_is_decoded_lossily_and_warned_about() {
+        let bytes = b"acme 1h\nCaf\xe9 on review";
+        assert_eq!(decode_as(bytes, false), "acme 1h\nCaf\u{fffd} on review");
+
+        let warnings = take_lossy_warnings();
+        assert!(warnings.contains(
+            &"invalid UTF-8 at byte 11 in \"Caf…\": e9 20 6f 6e 20 72 65 76".to_string()
+        ));
+
+        // Only once for the same output
+        decode_as(bytes, false);
+        assert!(!take_lossy_warnings().iter().any(|w| w.contains("Caf…")));
+    }
+
+    #[test]
+    fn latin1_locale_decodes_every_byte() {
+        assert_eq!(decode_as(b"Caf\xe9 \xbd h", true), "Café ½ h");
+        assert_eq!(decode_as("Café".as_bytes(), true), "Café");
+    }
+
+    #[test]
+    fn recognizes_latin1_locales() {
+        assert!(is_latin1("de_DE.ISO-8859-1"));
+        assert!(is_latin1("fr_FR.iso88591"));
+        assert!(is_latin1("en_US.CP1252"));
+        assert!(!is_latin1("en_US.UTF-8"));
+        assert!(!is_latin1("C"));
+        assert!(!is_latin1(""));
+    }
+}
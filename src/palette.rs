@@ -0,0 +1,26 @@
+// State of the ':' command palette
+#[derive(Debug, Default)]
+pub struct Palette {
+    pub query: String,
+    // Index into the filtered commands
+    pub selected: usize,
+}
+
+// Whether the characters of 'query' appear in 'text' in order, ignoring case,
+// e.g. 'swt' matches 'switch task'. Lower scores are tighter matches.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut position = 0;
+    let mut first = None;
+    let mut score = 0;
+
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let offset = text[position..].iter().position(|&c| c == q)?;
+        first.get_or_insert(position + offset);
+        // Gaps between matched characters make for a looser match
+        score += offset;
+        position += offset + 1;
+    }
+
+    Some(score + first.unwrap_or(0))
+}
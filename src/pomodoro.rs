@@ -0,0 +1,30 @@
+use std::time::{Duration, Instant};
+
+// A running pomodoro, counted down on the main screen
+#[derive(Debug, Clone, Copy)]
+pub struct PomodoroState {
+    started: Instant,
+    length: Duration,
+}
+
+impl PomodoroState {
+    pub fn new(minutes: u64) -> Self {
+        Self {
+            started: Instant::now(),
+            length: Duration::from_secs(minutes.max(1) * 60),
+        }
+    }
+
+    // How much of it is over, 0.0 to 1.0
+    pub fn progress(&self) -> f64 {
+        (self.started.elapsed().as_secs_f64() / self.length.as_secs_f64()).min(1.0)
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.length.saturating_sub(self.started.elapsed())
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.started.elapsed() >= self.length
+    }
+}
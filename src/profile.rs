@@ -0,0 +1,53 @@
+use serde::Deserialize;
+use std::{env, path::PathBuf, process::Command, sync::Mutex};
+
+// A separate zeit database, e.g.
+//   [[profiles]]
+//   name = "work"
+//   db = "~/.local/share/zeit/work.db"
+#[derive(Debug, Clone, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub db: String,
+}
+
+impl Profile {
+    fn db_path(&self) -> PathBuf {
+        match self.db.strip_prefix("~/") {
+            Some(rest) => env::var_os("HOME")
+                .map(|home| PathBuf::from(home).join(rest))
+                .unwrap_or_else(|| PathBuf::from(&self.db)),
+            None => PathBuf::from(&self.db),
+        }
+    }
+}
+
+// Database of the active profile. zeit is also run from worker threads that
+// only get a fn pointer, so this lives here instead of on the App.
+static ACTIVE_DB: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+pub fn activate(profile: &Profile) {
+    if let Ok(mut db) = ACTIVE_DB.lock() {
+        *db = Some(profile.db_path());
+    }
+}
+
+// The database zeit works on: the active profile's, else whatever ZEIT_DB says
+pub fn active_db() -> Option<PathBuf> {
+    ACTIVE_DB
+        .lock()
+        .ok()
+        .and_then(|db| db.clone())
+        .or_else(|| env::var_os("ZEIT_DB").map(PathBuf::from))
+}
+
+// A zeit command pointed at the active profile's database. 'program' is
+// zeit itself or a wrapper, 'prefix' the arguments before zeit's own.
+pub fn zeit(program: &str, prefix: &[String]) -> Command {
+    let mut command = Command::new(program);
+    command.args(prefix);
+    if let Some(db) = active_db() {
+        command.env("ZEIT_DB", db);
+    }
+    command
+}
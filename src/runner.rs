@@ -0,0 +1,325 @@
+use crate::{config::Config, error::ZeoxError, output::clean_output, profile};
+use std::{
+    env,
+    io::{self, BufRead, BufReader, Read},
+    process::{Child, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+// Everything zeox asks of zeit goes through here, so the binary can be
+// swapped for canned output
+pub trait ZeitRunner: Send + Sync {
+    // Run 'zeit <args>' and return its cleaned up stdout
+    fn run(&self, args: &[&str]) -> Result<String, ZeoxError>;
+
+    // The command line 'run' executes for 'args', for error reports and dry runs
+    fn argv(&self, args: &[&str]) -> Vec<String> {
+        std::iter::once("zeit".to_string())
+            .chain(bind_values(args))
+            .collect()
+    }
+
+    // Like 'run', but hands over each line of stdout as soon as it's printed
+    // instead of collecting them, so long output needn't be held at once
+    fn stream(&self, args: &[&str], on_line: &mut dyn FnMut(&str)) -> Result<(), ZeoxError> {
+        self.run(args)?.lines().for_each(on_line);
+        Ok(())
+    }
+}
+
+// zeit flags that take a value. They're passed as '--flag=value' so a name
+// like '-urgent' or an offset like '-0:15' is never taken for a flag itself.
+const VALUE_FLAGS: &[&str] = &["--project", "--task", "--notes", "--begin", "--finish"];
+
+// 'args' with every value flag joined to the value that follows it
+fn bind_values(args: &[&str]) -> Vec<String> {
+    let mut bound = Vec::new();
+    let mut args = args.iter();
+    while let Some(&arg) = args.next() {
+        match args.as_slice().first() {
+            Some(value) if VALUE_FLAGS.contains(&arg) => {
+                bound.push(format!("{}={}", arg, value));
+                args.next();
+            }
+            _ => bound.push(arg.to_string()),
+        }
+    }
+    bound
+}
+
+// Wait before the first retry, doubled for every further one (up to 32x)
+const RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+// How often a running zeit is checked on while waiting for it
+const WAIT_POLL: Duration = Duration::from_millis(20);
+
+// Wait for 'child' until 'timeout' is up, then kill it. None when it had to
+// be killed.
+fn wait_with_deadline(
+    child: &mut Child,
+    timeout: Option<Duration>,
+) -> io::Result<Option<ExitStatus>> {
+    let Some(timeout) = timeout else {
+        return child.wait().map(Some);
+    };
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        thread::sleep(WAIT_POLL);
+    }
+}
+
+// Read a pipe to the end on its own thread, so a chatty child can't fill it
+// up and stall while it's being waited for
+fn read_pipe(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        bytes
+    })
+}
+
+// The real zeit binary, pointed at the active profile
+pub struct ProcessRunner {
+    // zeit, or a wrapper and the arguments leading up to zeit's own
+    command: Vec<String>,
+    // Extra attempts when stderr matches one of 'transient'
+    retries: u32,
+    transient: Vec<String>,
+    // A zeit still running after this is killed, None waits forever
+    timeout: Option<Duration>,
+}
+
+impl ProcessRunner {
+    pub fn new(config: &Config) -> Self {
+        // An empty zeit_command is taken as the default rather than failing every call
+        let command = if config.zeit_command.is_empty() {
+            vec!["zeit".to_string()]
+        } else {
+            config.zeit_command.clone()
+        };
+
+        // ZEOX_ZEIT_TIMEOUT (seconds) wins over the config, 0 waits forever
+        let seconds = env::var("ZEOX_ZEIT_TIMEOUT")
+            .ok()
+            .and_then(|seconds| seconds.trim().parse().ok())
+            .unwrap_or(config.zeit_timeout_secs);
+
+        Self {
+            command,
+            retries: config.retries,
+            transient: config.transient_errors.clone(),
+            timeout: (seconds > 0).then(|| Duration::from_secs(seconds)),
+        }
+    }
+
+    fn zeit(&self) -> std::process::Command {
+        profile::zeit(&self.command[0], &self.command[1..])
+    }
+
+    fn run_once(&self, args: &[String], argv: &[String]) -> Result<String, ZeoxError> {
+        let spawn_error = |source| ZeoxError::Spawn {
+            argv: argv.to_vec(),
+            source,
+        };
+
+        let mut child = self
+            .zeit()
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(spawn_error)?;
+        let stdout = read_pipe(child.stdout.take());
+        let stderr = read_pipe(child.stderr.take());
+
+        let status = wait_with_deadline(&mut child, self.timeout).map_err(spawn_error)?;
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+
+        match status {
+            Some(status) if status.success() => Ok(clean_output(&stdout)),
+            Some(_) => Err(ZeoxError::Command {
+                argv: argv.to_vec(),
+                stderr: clean_output(&stderr),
+            }),
+            None => Err(ZeoxError::Timeout {
+                argv: argv.to_vec(),
+                after: self.timeout.unwrap_or_default(),
+            }),
+        }
+    }
+
+    // Whether trying again may help, e.g. another zeit holding the database lock
+    fn is_transient(&self, err: &ZeoxError) -> bool {
+        match err {
+            ZeoxError::Command { stderr, .. } => {
+                let stderr = stderr.to_lowercase();
+                self.transient
+                    .iter()
+                    .any(|pattern| stderr.contains(&pattern.to_lowercase()))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl ZeitRunner for ProcessRunner {
+    fn argv(&self, args: &[&str]) -> Vec<String> {
+        self.command
+            .iter()
+            .cloned()
+            .chain(bind_values(args))
+            .collect()
+    }
+
+    fn run(&self, args: &[&str]) -> Result<String, ZeoxError> {
+        let argv = self.argv(args);
+        let bound = bind_values(args);
+
+        let mut attempt = 0;
+        loop {
+            match self.run_once(&bound, &argv) {
+                Err(err) if attempt < self.retries && self.is_transient(&err) => {
+                    thread::sleep(RETRY_BACKOFF * 2u32.pow(attempt.min(5)));
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn stream(&self, args: &[&str], on_line: &mut dyn FnMut(&str)) -> Result<(), ZeoxError> {
+        let argv = self.argv(args);
+
+        let mut child = match self
+            .zeit()
+            .args(bind_values(args))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(source) => return Err(ZeoxError::Spawn { argv, source }),
+        };
+        let stdout = child.stdout.take();
+        let stderr = read_pipe(child.stderr.take());
+
+        // Killing a hung zeit also closes its stdout, which ends the read below
+        let timeout = self.timeout;
+        let waiter = thread::spawn(move || wait_with_deadline(&mut child, timeout));
+
+        let mut printed = false;
+        if let Some(stdout) = stdout {
+            for line in BufReader::new(stdout).split(b'\n').map_while(Result::ok) {
+                on_line(&clean_output(&line));
+                printed = true;
+            }
+        }
+
+        let stderr = stderr.join().unwrap_or_default();
+        let status = waiter
+            .join()
+            .unwrap_or_else(|_| Err(io::Error::other("zeit wait thread panicked")));
+
+        match status {
+            Ok(Some(status)) if status.success() => Ok(()),
+            Ok(Some(_)) => {
+                let err = ZeoxError::Command {
+                    argv,
+                    stderr: clean_output(&stderr),
+                };
+                // Transient failures get the retries of a plain run
+                if !printed && self.retries > 0 && self.is_transient(&err) {
+                    self.run(args)?.lines().for_each(on_line);
+                    Ok(())
+                } else {
+                    Err(err)
+                }
+            }
+            Ok(None) => Err(ZeoxError::Timeout {
+                argv,
+                after: self.timeout.unwrap_or_default(),
+            }),
+            Err(source) => Err(ZeoxError::Spawn { argv, source }),
+        }
+    }
+}
+
+// Canned 'zeit tracking' and 'zeit list' output for tests, every call is
+// recorded and anything else prints nothing
+#[cfg(test)]
+#[derive(Default)]
+pub struct CannedRunner {
+    pub tracking: String,
+    pub list: String,
+    pub calls: std::sync::Mutex<Vec<Vec<String>>>,
+}
+
+#[cfg(test)]
+impl ZeitRunner for CannedRunner {
+    fn run(&self, args: &[&str]) -> Result<String, ZeoxError> {
+        self.calls.lock().unwrap().push(self.argv(args));
+        Ok(match args.first() {
+            Some(&"tracking") => self.tracking.clone(),
+            Some(&"list") => self.list.clone(),
+            _ => String::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARGS: &[&str] = &[
+        "track",
+        "--project",
+        "-x",
+        "--task",
+        "a b",
+        "--notes",
+        "\"q\"",
+        "--no-colors",
+    ];
+    const BOUND: &[&str] = &[
+        "track",
+        "--project=-x",
+        "--task=a b",
+        "--notes=\"q\"",
+        "--no-colors",
+    ];
+
+    #[test]
+    fn values_are_bound_to_their_flags() {
+        assert_eq!(bind_values(ARGS), BOUND);
+        // A trailing flag without a value is left alone
+        assert_eq!(bind_values(&["list", "--project"]), ["list", "--project"]);
+    }
+
+    #[test]
+    fn values_reach_zeit_as_single_arguments() {
+        // printf stands in for zeit and prints every argument it got on a line
+        let config = Config {
+            zeit_command: vec!["printf".to_string(), "%s\\n".to_string()],
+            ..Config::default()
+        };
+        let runner = ProcessRunner::new(&config);
+
+        assert_eq!(runner.run(ARGS).unwrap().lines().collect::<Vec<_>>(), BOUND);
+        assert_eq!(runner.argv(ARGS)[2..], *BOUND);
+    }
+}
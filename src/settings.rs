@@ -0,0 +1,239 @@
+use crate::config::Config;
+use std::{fs, io};
+
+// Config values that can be changed from the Settings screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Setting {
+    DailyGoal,
+    IdleTimeout,
+    RoundTo,
+    StartupScreen,
+    RecentPicker,
+    ProjectColors,
+    AsciiMode,
+    Notifications,
+    HourlyReminder,
+    NotifyOnFinish,
+    DryRun,
+}
+
+impl Setting {
+    pub const ALL: [Setting; 11] = [
+        Setting::DailyGoal,
+        Setting::IdleTimeout,
+        Setting::RoundTo,
+        Setting::StartupScreen,
+        Setting::RecentPicker,
+        Setting::ProjectColors,
+        Setting::AsciiMode,
+        Setting::Notifications,
+        Setting::HourlyReminder,
+        Setting::NotifyOnFinish,
+        Setting::DryRun,
+    ];
+
+    // Name in zeox.toml
+    pub fn key(self) -> &'static str {
+        match self {
+            Setting::DailyGoal => "daily_goal_hours",
+            Setting::IdleTimeout => "idle_timeout_minutes",
+            Setting::RoundTo => "round_to_minutes",
+            Setting::StartupScreen => "startup_screen",
+            Setting::RecentPicker => "recent_picker",
+            Setting::ProjectColors => "project_colors",
+            Setting::AsciiMode => "ascii_mode",
+            Setting::Notifications => "notifications",
+            Setting::HourlyReminder => "hourly_reminder",
+            Setting::NotifyOnFinish => "notify_on_finish",
+            Setting::DryRun => "dry_run",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Setting::DailyGoal => "hours to aim for per day",
+            Setting::IdleTimeout => "minutes without input before idle",
+            Setting::RoundTo => "round begin/finish to minutes",
+            Setting::StartupScreen => "screen shown on launch",
+            Setting::RecentPicker => "pick from recent projects on start",
+            Setting::ProjectColors => "color projects in list and stats",
+            Setting::AsciiMode => "plain ASCII instead of glyphs",
+            Setting::Notifications => "desktop notifications",
+            Setting::HourlyReminder => "remind every hour of tracking",
+            Setting::NotifyOnFinish => "ring the bell on finish",
+            Setting::DryRun => "only show zeit commands",
+        }
+    }
+
+    // Whether the value is flipped rather than typed
+    pub fn is_toggle(self) -> bool {
+        !matches!(
+            self,
+            Setting::DailyGoal | Setting::IdleTimeout | Setting::RoundTo | Setting::StartupScreen
+        )
+    }
+
+    // The current value as shown on the Settings screen
+    pub fn value(self, config: &Config) -> String {
+        let on_off = |on: bool| if on { "on" } else { "off" }.to_string();
+        let unset = || "unset".to_string();
+        match self {
+            Setting::DailyGoal => config
+                .daily_goal_hours
+                .map_or_else(unset, |h| h.to_string()),
+            Setting::IdleTimeout => config
+                .idle_timeout_minutes
+                .map_or_else(unset, |m| m.to_string()),
+            Setting::RoundTo => config
+                .round_to_minutes
+                .map_or_else(unset, |m| m.to_string()),
+            Setting::StartupScreen => config.startup_screen.clone(),
+            Setting::RecentPicker => on_off(config.recent_picker),
+            Setting::ProjectColors => on_off(config.project_colors),
+            Setting::AsciiMode => config.ascii_mode.map_or_else(|| "auto".to_string(), on_off),
+            Setting::Notifications => on_off(config.notifications),
+            Setting::HourlyReminder => on_off(config.hourly_reminder),
+            Setting::NotifyOnFinish => on_off(config.notify_on_finish),
+            Setting::DryRun => on_off(config.dry_run),
+        }
+    }
+
+    // Flip a toggle, ascii_mode goes round auto, on and off
+    pub fn toggle(self, config: &mut Config) {
+        match self {
+            Setting::RecentPicker => config.recent_picker = !config.recent_picker,
+            Setting::ProjectColors => config.project_colors = !config.project_colors,
+            Setting::AsciiMode => {
+                config.ascii_mode = match config.ascii_mode {
+                    None => Some(true),
+                    Some(true) => Some(false),
+                    Some(false) => None,
+                }
+            }
+            Setting::Notifications => config.notifications = !config.notifications,
+            Setting::HourlyReminder => config.hourly_reminder = !config.hourly_reminder,
+            Setting::NotifyOnFinish => config.notify_on_finish = !config.notify_on_finish,
+            Setting::DryRun => config.dry_run = !config.dry_run,
+            _ => {}
+        }
+    }
+
+    // Set a typed value, empty input unsets the optional ones
+    pub fn set(self, config: &mut Config, input: &str) -> Result<(), String> {
+        let input = input.trim();
+        let number = |what: &str| -> Result<Option<u64>, String> {
+            if input.is_empty() {
+                return Ok(None);
+            }
+            input
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("Expected {}, e.g. 15, or nothing to unset", what))
+        };
+
+        match self {
+            Setting::DailyGoal => {
+                config.daily_goal_hours = if input.is_empty() {
+                    None
+                } else {
+                    match input.parse::<f64>() {
+                        Ok(hours) if hours > 0.0 && hours <= 24.0 => Some(hours),
+                        _ => return Err("Expected hours between 0 and 24, e.g. 7.5".to_string()),
+                    }
+                }
+            }
+            Setting::IdleTimeout => config.idle_timeout_minutes = number("minutes")?,
+            Setting::RoundTo => {
+                config.round_to_minutes = number("minutes")?
+                    .map(|m| u32::try_from(m).map_err(|_| "That's too many minutes".to_string()))
+                    .transpose()?
+            }
+            Setting::StartupScreen => {
+                if !["main", "list", "stats"].contains(&input) {
+                    return Err("Expected main, list or stats".to_string());
+                }
+                config.startup_screen = input.to_string();
+            }
+            _ => return Err(format!("{} is toggled, not typed", self.key())),
+        }
+        Ok(())
+    }
+
+    // Whether 'input' would be accepted by set()
+    pub fn check(self, input: &str) -> Result<(), String> {
+        self.set(&mut Config::default(), input)
+    }
+
+    // The value as it's written to zeox.toml, None to drop the key
+    fn toml(self, config: &Config) -> Option<String> {
+        match self {
+            Setting::DailyGoal => config.daily_goal_hours.map(|h| format!("{:?}", h)),
+            Setting::IdleTimeout => config.idle_timeout_minutes.map(|m| m.to_string()),
+            Setting::RoundTo => config.round_to_minutes.map(|m| m.to_string()),
+            Setting::StartupScreen => Some(format!("\"{}\"", config.startup_screen)),
+            Setting::AsciiMode => config.ascii_mode.map(|on| on.to_string()),
+            Setting::RecentPicker => Some(config.recent_picker.to_string()),
+            Setting::ProjectColors => Some(config.project_colors.to_string()),
+            Setting::Notifications => Some(config.notifications.to_string()),
+            Setting::HourlyReminder => Some(config.hourly_reminder.to_string()),
+            Setting::NotifyOnFinish => Some(config.notify_on_finish.to_string()),
+            Setting::DryRun => Some(config.dry_run.to_string()),
+        }
+    }
+}
+
+// Replace, add or (for None) drop the top-level 'key = ...' line, leaving
+// comments and everything else alone. New keys go above the first table.
+fn set_key(contents: &str, key: &str, value: Option<&str>) -> String {
+    let mut lines: Vec<String> = contents.lines().map(String::from).collect();
+    let tables = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with('['))
+        .unwrap_or(lines.len());
+    let existing = lines[..tables].iter().position(|line| {
+        line.split_once('=')
+            .is_some_and(|(name, _)| name.trim() == key)
+    });
+
+    match (existing, value) {
+        (Some(index), Some(value)) => lines[index] = format!("{} = {}", key, value),
+        (Some(index), None) => {
+            lines.remove(index);
+        }
+        (None, Some(value)) => {
+            // Right after the last top-level line rather than after the blank
+            // lines separating it from the tables
+            let at = lines[..tables]
+                .iter()
+                .rposition(|line| !line.trim().is_empty())
+                .map_or(0, |index| index + 1);
+            lines.insert(at, format!("{} = {}", key, value));
+        }
+        (None, None) => {}
+    }
+
+    let mut updated = lines.join("\n");
+    updated.push('\n');
+    updated
+}
+
+// Write 'setting' as it is in 'config' to zeox.toml, creating the file if
+// needed. Nothing is written that wouldn't load again.
+pub fn save(config: &Config, setting: Setting) -> Result<(), String> {
+    let path = Config::path().ok_or("Can't locate zeox.toml, HOME is not set")?;
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(format!("Failed to read {}: {}", path.display(), err)),
+    };
+
+    let updated = set_key(&contents, setting.key(), setting.toml(config).as_deref());
+    toml::from_str::<Config>(&updated)
+        .map_err(|err| format!("Not saved, {} would be invalid: {}", path.display(), err))?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("Failed to create {}: {}", dir.display(), err))?;
+    }
+    fs::write(&path, updated).map_err(|err| format!("Failed to write {}: {}", path.display(), err))
+}
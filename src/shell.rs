@@ -0,0 +1,47 @@
+// Split a command line into arguments the way a shell would for the simple
+// cases: whitespace separates, single and double quotes group, and a
+// backslash escapes the next character outside single quotes
+pub fn split_args(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    // Whether 'current' is an argument even if empty, e.g. ""
+    let mut started = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('\''), c) => current.push(c),
+            (_, '\\') => {
+                let escaped = chars.next().ok_or("Trailing backslash")?;
+                current.push(escaped);
+                started = true;
+            }
+            (Some(_), c) => current.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                started = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if started {
+                    args.push(std::mem::take(&mut current));
+                    started = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                started = true;
+            }
+        }
+    }
+
+    if let Some(q) = quote {
+        return Err(format!("Unterminated {} quote", q));
+    }
+    if started {
+        args.push(current);
+    }
+
+    Ok(args)
+}
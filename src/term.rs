@@ -0,0 +1,101 @@
+use crossterm::{
+    cursor::MoveTo,
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{
+        disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
+};
+use std::{
+    io,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+// Terminals without an alternate screen, where entering one is silently
+// ignored and leaving it doesn't give the old contents back
+const NO_ALTERNATE_TERMS: &[&str] = &["dumb", "linux", "vt52", "vt100", "vt102", "vt220"];
+
+// Whether the TUI runs on the alternate screen, or inline on the main one
+static ALTERNATE: AtomicBool = AtomicBool::new(true);
+// Set when the inline screen was written over, e.g. by a prompt, so the
+// next frame has to be drawn from scratch
+static REDRAW: AtomicBool = AtomicBool::new(false);
+
+// Take over the terminal. Falls back to drawing inline when the alternate
+// screen is unavailable; returns the notice to show when it did.
+pub fn enter() -> io::Result<Option<String>> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+
+    let unsupported = std::env::var("TERM")
+        .map(|term| NO_ALTERNATE_TERMS.contains(&term.as_str()))
+        .unwrap_or(false);
+    let notice = if unsupported {
+        Some("Terminal has no alternate screen, drawing inline".to_string())
+    } else {
+        execute!(stdout, EnterAlternateScreen)
+            .err()
+            .map(|err| format!("Alternate screen unavailable ({}), drawing inline", err))
+    };
+
+    if notice.is_some() {
+        ALTERNATE.store(false, Ordering::Relaxed);
+        execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+    }
+    execute!(stdout, EnableMouseCapture)?;
+
+    Ok(notice)
+}
+
+// Give the terminal back for good, undoing only what enter() did
+pub fn leave() -> io::Result<()> {
+    disable_raw_mode()?;
+    let mut stdout = io::stdout();
+    if ALTERNATE.load(Ordering::Relaxed) {
+        execute!(stdout, LeaveAlternateScreen, DisableMouseCapture)
+    } else {
+        // Don't leave the last frame under the shell prompt
+        execute!(
+            stdout,
+            DisableMouseCapture,
+            Clear(ClearType::All),
+            MoveTo(0, 0)
+        )
+    }
+}
+
+// Temporarily hand the terminal over, e.g. to requestty or an editor
+pub fn suspend() {
+    disable_raw_mode().unwrap();
+    if ALTERNATE.load(Ordering::Relaxed) {
+        execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture).unwrap();
+    } else {
+        execute!(io::stdout(), DisableMouseCapture).unwrap();
+    }
+}
+
+// Take the terminal back after suspend()
+pub fn resume() {
+    if ALTERNATE.load(Ordering::Relaxed) {
+        execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture).unwrap();
+    } else {
+        execute!(io::stdout(), EnableMouseCapture).unwrap();
+        REDRAW.store(true, Ordering::Relaxed);
+    }
+    enable_raw_mode().unwrap();
+}
+
+// Run 'f' with the terminal handed over, e.g. to requestty prompts or an
+// editor, and take it back afterwards
+pub fn suspended<T>(f: impl FnOnce() -> T) -> T {
+    suspend();
+    let result = f();
+    resume();
+    result
+}
+
+// Whether the screen has to be cleared before the next frame
+pub fn take_redraw() -> bool {
+    REDRAW.swap(false, Ordering::Relaxed)
+}
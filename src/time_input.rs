@@ -0,0 +1,232 @@
+use chrono::{DateTime, Duration, Local, NaiveDateTime, NaiveTime, TimeZone};
+
+// A begin/finish time as typed into a prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeInput {
+    Absolute(DateTime<Local>),
+    Relative(Duration),
+}
+
+impl TimeInput {
+    // The point in time this refers to, relative offsets count from 'now'
+    pub fn resolve(self, now: DateTime<Local>) -> DateTime<Local> {
+        match self {
+            TimeInput::Absolute(time) => time,
+            TimeInput::Relative(offset) => now + offset,
+        }
+    }
+
+    // Format the time the way 'zeit' expects for --begin/--finish
+    pub fn to_zeit_arg(self) -> String {
+        match self {
+            TimeInput::Absolute(time) => time.to_rfc3339(),
+            TimeInput::Relative(offset) => {
+                let sign = if offset < Duration::zero() { '-' } else { '+' };
+                let minutes = offset.num_minutes().abs();
+                format!("{}{}:{:02}", sign, minutes / 60, minutes % 60)
+            }
+        }
+    }
+}
+
+// Round to the nearest multiple of 'minutes' past local midnight, e.g. 09:08
+// becomes 09:15 for 15-minute increments
+pub fn round_to_increment(time: DateTime<Local>, minutes: u32) -> DateTime<Local> {
+    if minutes == 0 {
+        return time;
+    }
+
+    let midnight = time.date_naive().and_hms_opt(0, 0, 0).unwrap_or_default();
+    let elapsed = time.naive_local() - midnight;
+    let step = i64::from(minutes) * 60;
+    let rounded = (elapsed.num_seconds() + step / 2) / step * step;
+
+    Local
+        .from_local_datetime(&(midnight + Duration::seconds(rounded)))
+        .earliest()
+        .unwrap_or(time)
+}
+
+// Parse relative offsets ('-0:15', '+1:00', '-90m', '-1.5h'), RFC 3339
+// timestamps and any of the configured absolute formats. Formats without a
+// date refer to today.
+pub fn parse_time_input(input: &str, formats: &[String]) -> Result<TimeInput, String> {
+    let input = input.trim();
+
+    if let Some(offset) = parse_relative(input) {
+        return Ok(TimeInput::Relative(offset));
+    }
+
+    // Shorthand offsets need their sign here, a bare '90m' could mean either way
+    if input.starts_with(['-', '+']) {
+        if let Some(offset) = parse_duration_shorthand(input) {
+            return Ok(TimeInput::Relative(offset));
+        }
+    }
+
+    if let Ok(time) = DateTime::parse_from_rfc3339(input) {
+        return Ok(TimeInput::Absolute(time.with_timezone(&Local)));
+    }
+
+    for format in formats {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(input, format) {
+            if let Some(time) = Local.from_local_datetime(&naive).earliest() {
+                return Ok(TimeInput::Absolute(time));
+            }
+        }
+
+        if let Ok(naive) = NaiveTime::parse_from_str(input, format) {
+            let naive = Local::now().date_naive().and_time(naive);
+            if let Some(time) = Local.from_local_datetime(&naive).earliest() {
+                return Ok(TimeInput::Absolute(time));
+            }
+        }
+    }
+
+    Err(format!(
+        "Unrecognized time '{}', try HH:MM, -H:MM, -90m, -1.5h or {}",
+        input,
+        formats.join(", ")
+    ))
+}
+
+// A duration like '1.5h', '90m' or '-45m'. Hours may be fractional as long
+// as they come to whole minutes; anything without a unit, with several, or
+// with fractional minutes is rejected rather than guessed at.
+pub fn parse_duration_shorthand(input: &str) -> Option<Duration> {
+    let input = input.trim();
+    let (negative, rest) = match input.chars().next()? {
+        '-' => (true, &input[1..]),
+        '+' => (false, &input[1..]),
+        _ => (false, input),
+    };
+
+    let (number, minutes_per_unit) = if let Some(hours) = rest.strip_suffix('h') {
+        (hours, 60)
+    } else if let Some(minutes) = rest.strip_suffix('m') {
+        (minutes, 1)
+    } else {
+        return None;
+    };
+
+    // Only hours may have a fraction, counted exactly rather than as a float
+    let (whole, fraction) = match number.split_once('.') {
+        Some(_) if minutes_per_unit == 1 => return None,
+        Some((whole, fraction)) => (whole, fraction),
+        None => (number, ""),
+    };
+    let digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if whole.is_empty() || !digits(whole) || !digits(fraction) || number.ends_with('.') {
+        return None;
+    }
+
+    let scale = 10_i64.checked_pow(fraction.len() as u32)?;
+    let fraction_minutes = if fraction.is_empty() {
+        0
+    } else {
+        fraction
+            .parse::<i64>()
+            .ok()?
+            .checked_mul(minutes_per_unit)?
+    };
+    if fraction_minutes % scale != 0 {
+        return None;
+    }
+    let minutes = whole
+        .parse::<i64>()
+        .ok()?
+        .checked_mul(minutes_per_unit)?
+        .checked_add(fraction_minutes / scale)?;
+
+    let offset = Duration::try_minutes(minutes)?;
+    Some(if negative { -offset } else { offset })
+}
+
+fn parse_relative(input: &str) -> Option<Duration> {
+    let (negative, rest) = match input.chars().next()? {
+        '-' => (true, &input[1..]),
+        '+' => (false, &input[1..]),
+        _ => return None,
+    };
+
+    let (hours, minutes) = rest.split_once(':')?;
+    if !hours
+        .chars()
+        .chain(minutes.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let hours: i64 = hours.parse().ok()?;
+    let minutes: i64 = minutes.parse().ok()?;
+
+    if minutes >= 60 {
+        return None;
+    }
+
+    let offset = Duration::minutes(hours * 60 + minutes);
+    Some(if negative { -offset } else { offset })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minutes(input: &str) -> Option<i64> {
+        parse_duration_shorthand(input).map(|offset| offset.num_minutes())
+    }
+
+    #[test]
+    fn shorthand_durations() {
+        assert_eq!(minutes("1.5h"), Some(90));
+        assert_eq!(minutes("90m"), Some(90));
+        assert_eq!(minutes("-45m"), Some(-45));
+        assert_eq!(minutes("+2h"), Some(120));
+        assert_eq!(minutes("-0.25h"), Some(-15));
+        assert_eq!(minutes("1.1h"), Some(66));
+    }
+
+    #[test]
+    fn ambiguous_shorthand_is_rejected() {
+        for input in [
+            "90", "1.5", "h", "-m", ".5h", "1.h", "1.5.0h", "2.5m", "0.01h", "1h30m", "1 h", "--5m",
+        ] {
+            assert_eq!(minutes(input), None, "{}", input);
+        }
+    }
+
+    #[test]
+    fn time_input_needs_a_sign_for_shorthand() {
+        let formats = vec!["%H:%M".to_string()];
+        assert_eq!(
+            parse_time_input("-1.5h", &formats),
+            Ok(TimeInput::Relative(Duration::minutes(-90)))
+        );
+        assert_eq!(
+            parse_time_input("-0:15", &formats),
+            Ok(TimeInput::Relative(Duration::minutes(-15)))
+        );
+        assert!(parse_time_input("90m", &formats).is_err());
+    }
+
+    #[test]
+    fn rounds_to_nearest_increment() {
+        let at = |hour, minute| {
+            Local
+                .with_ymd_and_hms(2024, 6, 12, hour, minute, 0)
+                .single()
+                .unwrap()
+        };
+
+        assert_eq!(round_to_increment(at(9, 8), 15), at(9, 15));
+        assert_eq!(round_to_increment(at(9, 7), 15), at(9, 0));
+        assert_eq!(round_to_increment(at(9, 53), 15), at(10, 0));
+        assert_eq!(round_to_increment(at(9, 8), 0), at(9, 8));
+        assert_eq!(round_to_increment(at(9, 8), 1), at(9, 8));
+        assert_eq!(
+            round_to_increment(at(23, 59), 30),
+            at(0, 0) + Duration::days(1)
+        );
+    }
+}